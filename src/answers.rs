@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::inputs::{self, Inputs};
+use crate::registry::Registration;
+use crate::{runner, Params};
+
+/// The conventional location of the known answers file
+pub const ANSWERS_PATH: &str = "data/answers.toml";
+
+#[derive(Default, Deserialize)]
+struct DayAnswers {
+    part_one: Option<String>,
+    part_two: Option<String>,
+    /// The parameters the answers are for, e.g. the smaller preamble of an example
+    #[serde(default)]
+    params: BTreeMap<String, toml::Value>,
+}
+
+impl DayAnswers {
+    fn get(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Known answers for the puzzle inputs, keyed by year, day and part
+#[derive(Default)]
+pub struct Answers {
+    days: BTreeMap<(usize, usize), DayAnswers>,
+}
+
+impl Answers {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+
+        let content = read_to_string(path)
+            .map_err(|error| format!("Could not read {}: {}", path.display(), error))?;
+
+        Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let years: BTreeMap<String, BTreeMap<String, DayAnswers>> =
+            toml::from_str(content).map_err(|error| error.to_string())?;
+
+        let mut days = BTreeMap::new();
+
+        for (year, year_answers) in years {
+            let year = year
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid year {:?}", year))?;
+
+            for (day, day_answers) in year_answers {
+                let day = day
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid day {:?} in {}", day, year))?;
+
+                days.insert((year, day), day_answers);
+            }
+        }
+
+        Ok(Self { days })
+    }
+
+    pub fn get(&self, year: usize, day: usize, part: usize) -> Option<&str> {
+        self.days.get(&(year, day))?.get(part)
+    }
+
+    /// Every year and day that has at least one known answer
+    pub fn days(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.days.keys().copied()
+    }
+}
+
+/// Expected answers for the named inputs of a single day, keyed by input name and part
+#[derive(Default)]
+pub struct NamedAnswers {
+    inputs: BTreeMap<String, DayAnswers>,
+}
+
+impl NamedAnswers {
+    /// Loads the expected answers for the named inputs of a day, if there are any
+    pub fn load(dir: &Path, year: usize, day: usize) -> Result<Self, String> {
+        let path = inputs::named_dir(dir, year, day).join(inputs::NAMED_ANSWERS);
+
+        match read_to_string(&path) {
+            Ok(content) => {
+                Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let inputs = toml::from_str(content).map_err(|error| error.to_string())?;
+
+        Ok(Self { inputs })
+    }
+
+    pub fn get(&self, name: &str, part: usize) -> Option<&str> {
+        self.inputs.get(name)?.get(part)
+    }
+
+    /// The parameters to run a named input with, written as strings or plain values
+    pub fn params(&self, name: &str) -> Params {
+        let params = match self.inputs.get(name) {
+            Some(answers) => &answers.params,
+            None => return Params::default(),
+        };
+
+        params
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
+    /// The options to run a named input with, using its parameters unless they're given
+    pub fn options(&self, name: &str, options: &runner::Options) -> runner::Options {
+        runner::Options {
+            params: self.params(name).overridden_by(&options.params),
+            ..options.clone()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+/// The result of checking a single part against its known answer
+pub struct Verification {
+    pub outcome: runner::Outcome,
+    /// The name of the verified input, if it's not the puzzle input
+    pub input: Option<String>,
+    pub expected: Option<String>,
+    pub status: Status,
+}
+
+/// Runs the parts that have a known answer against the puzzle input in the input directory, and
+/// compares the results with the answers
+pub fn verify(
+    answers: &Answers,
+    dir: &Path,
+    registration: &Registration,
+    options: &runner::Options,
+) -> Vec<Verification> {
+    let (known, missing): (Vec<usize>, Vec<usize>) = [1, 2].iter().partition(|&&part| {
+        answers
+            .get(registration.year, registration.day, part)
+            .is_some()
+    });
+
+    let mut verifications = runner::run(registration, &Inputs::new(dir), &known, options)
+        .into_iter()
+        .map(|outcome| {
+            let expected = answers.get(outcome.year, outcome.day, outcome.part);
+
+            check(outcome, None, expected)
+        })
+        .chain(missing.into_iter().map(|part| Verification {
+            outcome: runner::Outcome {
+                year: registration.year,
+                day: registration.day,
+                part,
+                answer: Err(String::from("No known answer")),
+                parse_elapsed: Default::default(),
+                elapsed: Default::default(),
+            },
+            input: None,
+            expected: None,
+            status: Status::Missing,
+        }))
+        .collect::<Vec<Verification>>();
+
+    verifications.sort_by_key(|verification| verification.outcome.part);
+
+    verifications
+}
+
+/// Runs the parts that have an expected answer against every named input of a day, and compares
+/// the results with the answers
+pub fn verify_named(
+    dir: &Path,
+    registration: &Registration,
+    options: &runner::Options,
+) -> Vec<Verification> {
+    let (year, day) = (registration.year, registration.day);
+
+    let answers = match NamedAnswers::load(dir, year, day) {
+        Ok(answers) => answers,
+        Err(error) => {
+            return vec![Verification {
+                outcome: runner::Outcome {
+                    year,
+                    day,
+                    part: 1,
+                    answer: Err(error),
+                    parse_elapsed: Default::default(),
+                    elapsed: Default::default(),
+                },
+                input: None,
+                expected: None,
+                status: Status::Fail,
+            }];
+        }
+    };
+
+    inputs::names(dir, year, day)
+        .into_iter()
+        .flat_map(|name| {
+            let known = [1, 2]
+                .iter()
+                .copied()
+                .filter(|&part| answers.get(&name, part).is_some())
+                .collect::<Vec<usize>>();
+            let inputs = Inputs::new(dir).named(Some(name.clone()));
+            let options = answers.options(&name, options);
+
+            runner::run(registration, &inputs, &known, &options)
+                .into_iter()
+                .map(|outcome| {
+                    let expected = answers.get(&name, outcome.part);
+
+                    check(outcome, Some(name.clone()), expected)
+                })
+                .collect::<Vec<Verification>>()
+        })
+        .collect()
+}
+
+fn check(outcome: runner::Outcome, input: Option<String>, expected: Option<&str>) -> Verification {
+    let status = match (&outcome.answer, expected) {
+        (Ok(answer), Some(expected)) if answer.matches(expected) => Status::Pass,
+        _ => Status::Fail,
+    };
+
+    Verification {
+        outcome,
+        input,
+        expected: expected.map(String::from),
+        status,
+    }
+}
+
+/// Prints a table with the status of every verified part
+pub fn print_table(verifications: &[Verification]) {
+    let rows = verifications
+        .iter()
+        .map(|verification| {
+            let outcome = &verification.outcome;
+
+            vec![
+                outcome.year.to_string(),
+                outcome.day.to_string(),
+                verification.input.clone().unwrap_or_default(),
+                outcome.part.to_string(),
+                verification.status.to_string(),
+                verification.expected.clone().unwrap_or_default(),
+                match (&verification.status, &outcome.answer) {
+                    (Status::Missing, _) => String::new(),
+                    (_, Ok(answer)) => answer.to_string(),
+                    (_, Err(error)) => format!("error: {}", error),
+                },
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    runner::print_rows(
+        &[
+            "year", "day", "input", "part", "status", "expected", "actual",
+        ],
+        &rows,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pool, registry};
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse(
+            "[2020.01]
+part_one = \"876459\"
+part_two = \"116168640\"
+
+[2020.15]
+part_one = \"234\"",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(2020, 1, 1), Some("876459"));
+        assert_eq!(answers.get(2020, 1, 2), Some("116168640"));
+        assert_eq!(answers.get(2020, 15, 1), Some("234"));
+        assert_eq!(answers.get(2020, 15, 2), None);
+        assert_eq!(answers.get(2020, 2, 1), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_day() {
+        assert!(Answers::parse("[2020.first]\npart_one = \"1\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_named_answers() {
+        let answers = NamedAnswers::parse(
+            "[example1]
+part_one = \"35\"
+part_two = \"8\"
+
+[alice]
+part_two = \"19208\"
+params = { preamble = 5, bag = \"shiny gold\" }",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get("example1", 1), Some("35"));
+        assert_eq!(answers.get("example1", 2), Some("8"));
+        assert_eq!(answers.get("alice", 1), None);
+        assert_eq!(answers.get("alice", 2), Some("19208"));
+        assert_eq!(answers.get("bob", 1), None);
+        assert_eq!(answers.params("example1"), Params::default());
+        assert_eq!(
+            answers.params("alice"),
+            vec![
+                (String::from("bag"), String::from("shiny gold")),
+                (String::from("preamble"), String::from("5")),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn test_named_inputs() {
+        let verifications = pool::map(pool::default_threads(), &registry::all(), |registration| {
+            verify_named(
+                Path::new(inputs::INPUT_DIR),
+                registration,
+                &runner::Options::default(),
+            )
+        });
+
+        for verification in verifications.iter().flatten() {
+            let outcome = &verification.outcome;
+
+            assert!(
+                verification.status != Status::Fail,
+                "{}/{} {} part {}: expected {:?}, got {:?}",
+                outcome.year,
+                outcome.day,
+                verification.input.as_deref().unwrap_or_default(),
+                outcome.part,
+                verification.expected,
+                outcome.answer
+            );
+        }
+    }
+
+    #[test]
+    fn test_known_answers() {
+        let answers = Answers::load(ANSWERS_PATH).unwrap();
+
+        let registrations = answers
+            .days()
+            .map(|(year, day)| {
+                registry::find(year, day)
+                    .unwrap_or_else(|| panic!("{}/{} has answers but no solver", year, day))
+            })
+            .collect::<Vec<&Registration>>();
+
+        let verifications = pool::map(pool::default_threads(), &registrations, |registration| {
+            verify(
+                &answers,
+                Path::new(inputs::INPUT_DIR),
+                registration,
+                &runner::Options::default(),
+            )
+        });
+
+        for verification in verifications.iter().flatten() {
+            let outcome = &verification.outcome;
+
+            assert!(
+                verification.status != Status::Fail,
+                "{}/{} part {}: expected {:?}, got {:?}",
+                outcome.year,
+                outcome.day,
+                outcome.part,
+                verification.expected,
+                outcome.answer
+            );
+        }
+    }
+}
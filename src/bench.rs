@@ -0,0 +1,182 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::runner;
+use crate::{Context, Params, Solver};
+
+/// Summary statistics over the durations of repeated runs of a part
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+
+        let iterations = samples.len();
+        let middle = iterations / 2;
+
+        let median = if iterations.is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        let mean = samples.iter().sum::<Duration>() / iterations as u32;
+
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / iterations as f64;
+
+        Self {
+            iterations,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// The benchmark of a single part of a registered solver
+pub struct Benchmark {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub stats: Result<Stats, String>,
+}
+
+/// Parses the input once, and runs a part on it with the given parameters the given number of
+/// times, stopping at the first failing run
+pub fn bench(
+    day: &dyn Solver,
+    part: usize,
+    input: &str,
+    params: &Params,
+    iterations: usize,
+) -> Result<Stats, String> {
+    if iterations == 0 {
+        return Err(String::from("At least one iteration is required"));
+    }
+
+    params.check(day.params())?;
+    let ctx = Context::default().with_params(params.clone());
+
+    let parsed = runner::parse(day, input)?;
+
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(runner::solve(day, part, black_box(parsed.as_ref()), &ctx)?);
+
+            Ok(start.elapsed())
+        })
+        .collect::<Result<Vec<Duration>, String>>()?;
+
+    Ok(Stats::from_samples(samples))
+}
+
+/// Prints a table with the statistics of every benchmark
+pub fn print_table(benchmarks: &[Benchmark]) {
+    let rows = benchmarks
+        .iter()
+        .map(|benchmark| {
+            let mut row = vec![
+                benchmark.year.to_string(),
+                benchmark.day.to_string(),
+                benchmark.part.to_string(),
+            ];
+
+            match &benchmark.stats {
+                Ok(stats) => row.extend(vec![
+                    stats.iterations.to_string(),
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.mean),
+                    format!("{:.2?}", stats.stddev),
+                ]),
+                Err(error) => row.push(format!("error: {}", error)),
+            }
+
+            row
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    runner::print_rows(
+        &[
+            "year", "day", "part", "runs", "min", "median", "mean", "stddev",
+        ],
+        &rows,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(vec![
+            Duration::from_millis(4),
+            Duration::from_millis(2),
+            Duration::from_millis(6),
+            Duration::from_millis(8),
+        ]);
+
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert!((stats.stddev.as_secs_f64() - 0.001 * 5f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bench_registered_day() {
+        let day = (registry::find(2020, 1).unwrap().build)();
+        let stats = bench(
+            day.as_ref(),
+            1,
+            "1721\n979\n366\n299\n675\n1456",
+            &Params::default(),
+            5,
+        )
+        .unwrap();
+
+        assert_eq!(stats.iterations, 5);
+        assert!(stats.min <= stats.median);
+        assert!(stats.min <= stats.mean);
+    }
+
+    #[test]
+    fn test_bench_failing_day() {
+        let day = (registry::find(2020, 1).unwrap().build)();
+
+        assert_eq!(
+            bench(day.as_ref(), 1, "220\n1010", &Params::default(), 5),
+            Err(String::from("no solution, no matches"))
+        );
+    }
+
+    #[test]
+    fn test_bench_params() {
+        let day = (registry::find(2020, 1).unwrap().build)();
+        let param = |name: &str| {
+            vec![(name.to_string(), String::from("1230"))]
+                .into_iter()
+                .collect::<Params>()
+        };
+
+        assert!(bench(day.as_ref(), 1, "220\n1010", &param("target"), 5).is_ok());
+        assert!(bench(day.as_ref(), 1, "220\n1010", &param("goal"), 5)
+            .unwrap_err()
+            .starts_with("unknown parameter goal"));
+    }
+}
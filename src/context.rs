@@ -0,0 +1,188 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::Mutex;
+
+use crate::{Param, Params, SolveError};
+
+/// A handle for cancelling the parts that share it, e.g. when they run for too long
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Something a part reports while it's being solved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    Progress { current: usize, total: usize },
+    Log(String),
+}
+
+/// Receives the events reported by a part, e.g. to render its progress
+pub trait Reporter: Send + Sync {
+    fn report(&self, event: Event);
+}
+
+/// The run a part is solved within, passed to every part
+#[derive(Default)]
+pub struct Context {
+    token: CancellationToken,
+    reporter: Option<Arc<dyn Reporter>>,
+    params: Params,
+}
+
+impl Context {
+    pub fn new(token: CancellationToken) -> Self {
+        Self {
+            token,
+            reporter: None,
+            params: Params::default(),
+        }
+    }
+
+    /// Overrides the defaults of the parameters of the day
+    pub fn with_params(self, params: Params) -> Self {
+        Self { params, ..self }
+    }
+
+    /// Passes the events reported by the part on to the reporter, instead of discarding them
+    pub fn reporting_to(self, reporter: Arc<dyn Reporter>) -> Self {
+        Self {
+            reporter: Some(reporter),
+            ..self
+        }
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Fails once the run is cancelled, so long running loops can stop with `ctx.check()?`
+    pub fn check(&self) -> Result<(), SolveError> {
+        if self.is_cancelled() {
+            Err(SolveError::cancelled())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The value of a parameter of the day, which is its default unless it's overridden
+    pub fn param<T: FromStr>(&self, param: &Param) -> Result<T, SolveError> {
+        let value = self.params.get(param);
+
+        value
+            .parse()
+            .map_err(|_| SolveError::invalid(param.name, value))
+    }
+
+    /// Reports how far along the part is, e.g. the current turn out of the total number of turns
+    pub fn progress(&self, current: usize, total: usize) {
+        self.report(Event::Progress { current, total });
+    }
+
+    /// Reports a message about how the part is going
+    pub fn log<S: Into<String>>(&self, message: S) {
+        self.report(Event::Log(message.into()));
+    }
+
+    fn report(&self, event: Event) {
+        if let Some(reporter) = &self.reporter {
+            reporter.report(event);
+        }
+    }
+}
+
+/// Records every reported event, so tests can assert on them
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct Recorder(Mutex<Vec<Event>>);
+
+#[cfg(test)]
+impl Recorder {
+    pub(crate) fn events(&self) -> Vec<Event> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+impl Reporter for Recorder {
+    fn report(&self, event: Event) {
+        self.0.lock().unwrap().push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    #[test]
+    fn test_cancel() {
+        let token = CancellationToken::default();
+        let ctx = Context::new(token.clone());
+
+        assert_eq!(ctx.check(), Ok(()));
+
+        token.cancel();
+
+        assert!(ctx.is_cancelled());
+        assert_eq!(ctx.check().unwrap_err().kind, ErrorKind::Cancelled);
+    }
+
+    #[test]
+    fn test_param() {
+        const PREAMBLE: Param = Param {
+            name: "preamble",
+            default: "25",
+            description: "The number of preceding numbers a number is checked against",
+        };
+
+        let params = vec![(String::from("preamble"), String::from("five"))]
+            .into_iter()
+            .collect::<Params>();
+
+        assert_eq!(Context::default().param::<usize>(&PREAMBLE), Ok(25));
+        assert_eq!(
+            Context::default()
+                .with_params(params)
+                .param::<usize>(&PREAMBLE)
+                .unwrap_err()
+                .kind,
+            ErrorKind::Invalid("preamble")
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let recorder = Arc::new(Recorder::default());
+        let ctx = Context::default().reporting_to(recorder.clone());
+
+        ctx.progress(1, 4);
+        ctx.log("halfway");
+        Context::default().progress(2, 4);
+
+        assert_eq!(
+            recorder.events(),
+            vec![
+                Event::Progress {
+                    current: 1,
+                    total: 4
+                },
+                Event::Log(String::from("halfway")),
+            ]
+        );
+    }
+}
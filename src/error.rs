@@ -0,0 +1,174 @@
+use std::str::FromStr;
+
+/// The kind of failure a solver ran into
+#[derive(Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// A value in the input was expected to be a number
+    NotANumber,
+    /// A required part of the input, such as a field, section or separator, was not found
+    Missing(&'static str),
+    /// A part of the input was found, but it isn't one of the recognized values
+    Invalid(&'static str),
+    /// The input was understood, but it doesn't have an answer
+    NoSolution(&'static str),
+    /// The part was cancelled before it found an answer
+    Cancelled,
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ErrorKind::NotANumber => write!(f, "not a number"),
+            ErrorKind::Missing(what) => write!(f, "missing {}", what),
+            ErrorKind::Invalid(what) => write!(f, "invalid {}", what),
+            ErrorKind::NoSolution(reason) => write!(f, "no solution, {}", reason),
+            ErrorKind::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// An error from solving a puzzle, pointing at the offending part of the input when possible
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveError {
+    pub kind: ErrorKind,
+    /// The one-based line number in the input
+    pub line: Option<usize>,
+    /// The offending text from the input
+    pub text: Option<String>,
+}
+
+impl SolveError {
+    pub fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            line: None,
+            text: None,
+        }
+    }
+
+    pub fn not_a_number(text: &str) -> Self {
+        Self::new(ErrorKind::NotANumber).with_text(text)
+    }
+
+    pub fn missing(what: &'static str) -> Self {
+        Self::new(ErrorKind::Missing(what))
+    }
+
+    pub fn invalid(what: &'static str, text: &str) -> Self {
+        Self::new(ErrorKind::Invalid(what)).with_text(text)
+    }
+
+    pub fn no_solution(reason: &'static str) -> Self {
+        Self::new(ErrorKind::NoSolution(reason))
+    }
+
+    pub fn cancelled() -> Self {
+        Self::new(ErrorKind::Cancelled)
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Attaches the zero-based line index, unless a more precise line is already known
+    pub fn on_line(mut self, index: usize) -> Self {
+        if self.line.is_none() {
+            self.line = Some(index + 1);
+        }
+
+        self
+    }
+
+    /// Attaches the line of a slice borrowed from the input, e.g. a line within a section
+    pub fn within(self, input: &str, slice: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (slice.as_ptr() as usize).wrapping_sub(start);
+
+        if offset <= input.len() {
+            let index = input[..offset].matches('\n').count();
+
+            self.on_line(index)
+        } else {
+            self
+        }
+    }
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+
+        write!(f, "{}", self.kind)?;
+
+        if let Some(text) = &self.text {
+            write!(f, " {:?}", text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Parses a number, reporting the text as the offending input if it isn't one
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, SolveError> {
+    text.parse::<T>()
+        .map_err(|_| SolveError::not_a_number(text))
+}
+
+/// Parses every line of the input, attaching the line number to the first error
+pub fn parse_lines<'a, T, F>(input: &'a str, mut parse: F) -> Result<Vec<T>, SolveError>
+where
+    F: FnMut(&'a str) -> Result<T, SolveError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|error| error.on_line(index)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = SolveError::invalid("operation", "xyz +1").on_line(2);
+
+        assert_eq!(error.to_string(), "line 3: invalid operation \"xyz +1\"");
+        assert_eq!(
+            SolveError::no_solution("no two entries sum to 2020").to_string(),
+            "no solution, no two entries sum to 2020"
+        );
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines("1\n2\n3", parse_number::<usize>),
+            Ok(vec![1, 2, 3])
+        );
+
+        let error = parse_lines("1\n2\nthree", parse_number::<usize>).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::NotANumber);
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.text, Some(String::from("three")));
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "rules\n\nyour ticket:\n7,x,14";
+        let section = input.split("\n\n").nth(1).unwrap();
+        let ticket = section.lines().nth(1).unwrap();
+
+        let error = SolveError::not_a_number("x").within(input, ticket);
+        assert_eq!(error.line, Some(4));
+
+        let error = SolveError::not_a_number("x").within(input, "not borrowed from the input");
+        assert_eq!(error.line, None);
+    }
+}
@@ -0,0 +1,353 @@
+use std::convert::TryFrom;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::visualize::{Colour, Frame};
+
+/// How many pixels wide and tall each tile is drawn when no size is given
+pub const CELL_SIZE: usize = 4;
+
+/// Writes the frames to an animated GIF if the path ends in .gif, and otherwise to a directory
+/// with a PNG per frame, named by the number of the frame, e.g. 0001.png. The frames of an earlier
+/// export to the directory are removed first, so a shorter run doesn't leave any behind. Returns
+/// the written files
+pub fn export(
+    frames: &[Frame],
+    path: &Path,
+    cell_size: usize,
+    delay: Duration,
+) -> Result<Vec<PathBuf>, String> {
+    let write_error =
+        |path: &Path, error: String| format!("Could not write {}: {}", path.display(), error);
+
+    let create_error =
+        |path: &Path, error: io::Error| format!("Could not create {}: {}", path.display(), error);
+
+    if is_gif(path) {
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|error| create_error(directory, error))?;
+        }
+
+        let file = File::create(path).map_err(|error| write_error(path, error.to_string()))?;
+
+        write_gif(BufWriter::new(file), frames, cell_size, delay)
+            .map_err(|error| write_error(path, error))?;
+
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    fs::create_dir_all(path).map_err(|error| create_error(path, error))?;
+    remove_frames(path)?;
+
+    // The palette is shared by every frame, so a tile has the same colour in each of them
+    let palette = palette(frames)?;
+
+    frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let path = path.join(format!("{:04}.png", index + 1));
+            let file =
+                File::create(&path).map_err(|error| write_error(&path, error.to_string()))?;
+
+            write_png(BufWriter::new(file), frame, &palette, cell_size)
+                .map_err(|error| write_error(&path, error))?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Where to export the frames of a single part, when more than one part is exported, e.g.
+/// day11-part2.gif, or a part2 directory within the directory
+pub fn part_path(path: &Path, part: usize) -> PathBuf {
+    if !is_gif(path) {
+        return path.join(format!("part{}", part));
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+    path.with_file_name(format!("{}-part{}.gif", stem, part))
+}
+
+/// Removes the frames of an earlier export from the directory, leaving any other files alone
+fn remove_frames(dir: &Path) -> Result<(), String> {
+    let read_error = |error: io::Error| format!("Could not read {}: {}", dir.display(), error);
+
+    for entry in fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+
+        if is_frame(&path) {
+            fs::remove_file(&path)
+                .map_err(|error| format!("Could not remove {}: {}", path.display(), error))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the file is a frame, named by its number, e.g. 0001.png
+fn is_frame(path: &Path) -> bool {
+    let is_png = path.extension().is_some_and(|extension| extension == "png");
+    let is_number = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem.len() >= 4 && stem.bytes().all(|byte| byte.is_ascii_digit()));
+
+    is_png && is_number && path.is_file()
+}
+
+fn is_gif(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"))
+}
+
+/// Every colour of the frames, in the order they first appear, so the same frames always get the
+/// same palette. Images have room for 256 colours at most
+pub fn palette(frames: &[Frame]) -> Result<Vec<Colour>, String> {
+    let mut palette = Vec::new();
+
+    for (_, cell) in frames.iter().flat_map(|frame| frame.iter()) {
+        if palette.contains(&cell.colour) {
+            continue;
+        }
+
+        if palette.len() == 256 {
+            return Err(String::from("The frames have more than 256 colours"));
+        }
+
+        palette.push(cell.colour);
+    }
+
+    Ok(palette)
+}
+
+/// Writes the frames as an animated GIF that loops forever
+pub fn write_gif<W: Write>(
+    writer: W,
+    frames: &[Frame],
+    cell_size: usize,
+    delay: Duration,
+) -> Result<(), String> {
+    let first = frames
+        .first()
+        .ok_or_else(|| String::from("There are no frames"))?;
+
+    if frames
+        .iter()
+        .any(|frame| (frame.width(), frame.height()) != (first.width(), first.height()))
+    {
+        return Err(String::from("The frames differ in size"));
+    }
+
+    let (width, height) = size(first, cell_size)?;
+    let palette = palette(frames)?;
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &rgb(&palette))
+        .map_err(|error| error.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|error| error.to_string())?;
+
+    for frame in frames {
+        let mut image = gif::Frame::from_indexed_pixels(
+            width,
+            height,
+            pixels(frame, &palette, cell_size),
+            None,
+        );
+        // GIFs count the delay in hundredths of a second
+        image.delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+        encoder
+            .write_frame(&image)
+            .map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Writes a single frame as a PNG, with the colours of the palette
+pub fn write_png<W: Write>(
+    writer: W,
+    frame: &Frame,
+    palette: &[Colour],
+    cell_size: usize,
+) -> Result<(), String> {
+    let (width, height) = size(frame, cell_size)?;
+
+    let mut encoder = png::Encoder::new(writer, u32::from(width), u32::from(height));
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(rgb(palette));
+
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels(frame, palette, cell_size)))
+        .map_err(|error| error.to_string())
+}
+
+/// The size of a frame in pixels, which images limit to 65535 in either direction
+fn size(frame: &Frame, cell_size: usize) -> Result<(u16, u16), String> {
+    let pixels = |cells: usize| {
+        u16::try_from(cells * cell_size)
+            .ok()
+            .filter(|&pixels| pixels > 0)
+    };
+
+    match (pixels(frame.width()), pixels(frame.height())) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(format!(
+            "A frame of {}x{} tiles of {} pixels doesn't fit in an image",
+            frame.width(),
+            frame.height(),
+            cell_size
+        )),
+    }
+}
+
+fn rgb(palette: &[Colour]) -> Vec<u8> {
+    palette
+        .iter()
+        .flat_map(|colour| [colour.red, colour.green, colour.blue])
+        .collect()
+}
+
+/// The index in the palette of every pixel, row by row, where each tile is a square of pixels
+fn pixels(frame: &Frame, palette: &[Colour], cell_size: usize) -> Vec<u8> {
+    frame
+        .rows()
+        .flat_map(|row| {
+            let line = row
+                .iter()
+                .flat_map(|cell| {
+                    let index = palette
+                        .iter()
+                        .position(|&colour| colour == cell.colour)
+                        .expect("Every colour of the frame is in the palette");
+
+                    std::iter::repeat_n(index as u8, cell_size)
+                })
+                .collect::<Vec<u8>>();
+
+            std::iter::repeat_n(line, cell_size).flatten()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Grid;
+    use crate::visualize::Cell;
+
+    const RED: Colour = Colour::rgb(255, 0, 0);
+    const BLUE: Colour = Colour::rgb(0, 0, 255);
+
+    fn frame(input: &str) -> Frame {
+        Grid::parse(input, |glyph| {
+            let colour = if glyph == '#' { RED } else { BLUE };
+
+            Ok(Cell { glyph, colour })
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pixels() {
+        let frame = frame("#.\n..");
+        let palette = palette(std::slice::from_ref(&frame)).unwrap();
+
+        assert_eq!(palette, vec![RED, BLUE]);
+        assert_eq!(
+            pixels(&frame, &palette, 2),
+            vec![0, 0, 1, 1, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames = vec![frame("#.\n.."), frame(".#\n.."), frame("..\n.#")];
+        let mut gif = Vec::new();
+        write_gif(&mut gif, &frames, 3, Duration::from_millis(200)).unwrap();
+
+        // The same frames always make the same image, so images can be compared across runs
+        let mut again = Vec::new();
+        write_gif(&mut again, &frames, 3, Duration::from_millis(200)).unwrap();
+        assert_eq!(gif, again);
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+
+        assert_eq!((decoder.width(), decoder.height()), (6, 6));
+
+        let mut decoded = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            decoded.push((frame.delay, frame.buffer[..4].to_vec()));
+        }
+
+        assert_eq!(
+            decoded,
+            vec![
+                (20, vec![255, 0, 0, 255]),
+                (20, vec![0, 0, 255, 255]),
+                (20, vec![0, 0, 255, 255]),
+            ]
+        );
+        assert!(write_gif(Vec::new(), &[frame("#"), frame("##")], 1, Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_png() {
+        let frame = frame("#.\n.#");
+        let mut png = Vec::new();
+        write_png(&mut png, &frame, &[BLUE, RED], 1).unwrap();
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!(
+            reader.info().palette.as_deref(),
+            Some(&[0, 0, 255, 255, 0, 0][..])
+        );
+        assert_eq!(pixels, vec![1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_export_removes_earlier_frames() {
+        let dir = std::env::temp_dir().join(format!("adventofcode-frames-{}", std::process::id()));
+        let frames = vec![frame("#."), frame(".#"), frame("..")];
+
+        export(&frames, &dir, 1, Duration::ZERO).unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let written = export(&frames[..1], &dir, 1, Duration::ZERO).unwrap();
+        let mut files = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<String>>();
+        files.sort();
+
+        assert_eq!(written, vec![dir.join("0001.png")]);
+        assert_eq!(files, vec!["0001.png", "notes.txt"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("out/day11.gif"), 2),
+            PathBuf::from("out/day11-part2.gif")
+        );
+        assert_eq!(
+            part_path(Path::new("out/frames"), 1),
+            PathBuf::from("out/frames/part1")
+        );
+    }
+}
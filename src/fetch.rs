@@ -0,0 +1,245 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The site the puzzle inputs are fetched from, unless another base URL is configured
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the CLI to the site, so the maintainers know who to contact about the traffic
+pub const USER_AGENT: &str = "github.com/nicklasmoeller/adventofcode by hello@nicklasmoeller.com";
+
+/// The minimum time between two requests to the site
+pub const INTERVAL: Duration = Duration::from_secs(5);
+
+/// Fetches puzzle inputs for a logged in user, identified by their session token
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    agent: ureq::Agent,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Fetcher {
+    /// Creates a fetcher that waits at least the given interval between two requests
+    pub fn new(base_url: &str, session: &str, interval: Duration) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            interval,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Fetches the puzzle input for a given year and day
+    pub fn input(&self, year: usize, day: usize) -> Result<String, String> {
+        let input = self.get(&format!("/{}/day/{}/input", year, day))?;
+
+        if input.is_empty() {
+            return Err(format!("The input for {}/{} is empty", year, day));
+        }
+
+        Ok(input)
+    }
+
+    /// Posts an answer for a given year, day and part, returning the page with the response
+    pub fn answer(
+        &self,
+        year: usize,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> Result<String, String> {
+        let level = part.to_string();
+
+        self.send(
+            &format!("/{}/day/{}/answer", year, day),
+            Some(&[("level", &level), ("answer", answer)]),
+        )
+    }
+
+    fn get(&self, path: &str) -> Result<String, String> {
+        self.send(path, None)
+    }
+
+    /// Sends a GET request, or a POST request if there's a form
+    fn send(&self, path: &str, form: Option<&[(&str, &str)]>) -> Result<String, String> {
+        let url = format!("{}{}", self.base_url, path);
+
+        self.throttle();
+
+        let cookie = format!("session={}", self.session);
+        let response = match form {
+            Some(form) => self.agent.post(&url).set("Cookie", &cookie).send_form(form),
+            None => self.agent.get(&url).set("Cookie", &cookie).call(),
+        };
+
+        let response = response.map_err(|error| match error {
+            ureq::Error::Status(404, _) => {
+                format!("Could not fetch {}: the puzzle isn't unlocked yet", url)
+            }
+            ureq::Error::Status(400, _) | ureq::Error::Status(500, _) => format!(
+                "Could not fetch {}: the session token is missing or expired",
+                url
+            ),
+            ureq::Error::Status(status, _) => format!("Could not fetch {}: {}", url, status),
+            error => format!("Could not fetch {}: {}", url, error),
+        })?;
+
+        response
+            .into_string()
+            .map_err(|error| format!("Could not read {}: {}", url, error))
+    }
+
+    /// Sleeps until the interval since the previous request has passed
+    fn throttle(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(wait) = last_request
+            .map(|last_request| self.interval.saturating_sub(last_request.elapsed()))
+            .filter(|wait| !wait.is_zero())
+        {
+            thread::sleep(wait);
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// A stand-in for the site, serving canned responses on a local port
+#[cfg(test)]
+pub mod mock {
+    use std::thread::{self, JoinHandle};
+
+    /// A request received by the mock server
+    pub struct Request {
+        pub method: String,
+        pub url: String,
+        pub cookie: Option<String>,
+        pub user_agent: Option<String>,
+        pub body: String,
+    }
+
+    /// Serves the given status codes and bodies, one per request, in order. Returns the base URL
+    /// of the server, and a handle that yields the received requests once every response is
+    /// served
+    pub fn serve(responses: Vec<(u16, &str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr());
+        let responses = responses
+            .into_iter()
+            .map(|(status, body)| (status, body.to_string()))
+            .collect::<Vec<(u16, String)>>();
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let mut request = server.recv().unwrap();
+
+                    let header = |name: &str| {
+                        request
+                            .headers()
+                            .iter()
+                            .find(|header| {
+                                header.field.as_str().as_str().eq_ignore_ascii_case(name)
+                            })
+                            .map(|header| header.value.to_string())
+                    };
+
+                    let mut received = Request {
+                        method: request.method().to_string(),
+                        url: request.url().to_string(),
+                        cookie: header("Cookie"),
+                        user_agent: header("User-Agent"),
+                        body: String::new(),
+                    };
+
+                    request
+                        .as_reader()
+                        .read_to_string(&mut received.body)
+                        .unwrap();
+                    request
+                        .respond(tiny_http::Response::from_string(body).with_status_code(status))
+                        .unwrap();
+
+                    received
+                })
+                .collect()
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_input() {
+        let (base_url, server) = mock::serve(vec![(200, "1721\n979\n")]);
+
+        let input = Fetcher::new(&base_url, "abc123\n", INTERVAL).input(2020, 1);
+        let requests = server.join().unwrap();
+
+        assert_eq!(input, Ok(String::from("1721\n979\n")));
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].url, "/2020/day/1/input");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+        assert_eq!(requests[0].user_agent.as_deref(), Some(USER_AGENT));
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, server) = mock::serve(vec![
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (200, ""),
+        ]);
+
+        let fetcher = Fetcher::new(&base_url, "abc123", Duration::ZERO);
+
+        assert!(fetcher
+            .input(2020, 26)
+            .unwrap_err()
+            .ends_with("the puzzle isn't unlocked yet"));
+        assert!(fetcher
+            .input(2020, 1)
+            .unwrap_err()
+            .ends_with("the session token is missing or expired"));
+        assert_eq!(
+            fetcher.input(2020, 1),
+            Err(String::from("The input for 2020/1 is empty"))
+        );
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let (base_url, server) = mock::serve(vec![(200, "1"), (200, "2")]);
+
+        let fetcher = Fetcher::new(&base_url, "abc123", Duration::from_millis(200));
+        let start = Instant::now();
+
+        assert_eq!(fetcher.input(2020, 1), Ok(String::from("1")));
+        assert_eq!(fetcher.input(2020, 2), Ok(String::from("2")));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,214 @@
+use std::fs::{create_dir_all, read_dir, read_to_string, write};
+use std::io::{self, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+use crate::fetch::Fetcher;
+
+/// The conventional directory of the puzzle inputs
+pub const INPUT_DIR: &str = "data";
+
+/// The name of the file with the expected answers for the named inputs of a day
+pub const NAMED_ANSWERS: &str = "answers.toml";
+
+/// A source of puzzle inputs, shared between the threads running the days
+pub trait Provider: Sync {
+    /// Reads the input for a given year and day
+    fn read(&self, year: usize, day: usize) -> Result<String, String>;
+}
+
+/// Where to find the puzzle inputs: either the input of each day, laid out as <year>/<day>.txt,
+/// or a named input of each day, laid out as <year>/<day>/<name>.txt. Missing puzzle inputs are
+/// fetched and stored in the directory, if there's a fetcher
+pub struct Inputs {
+    dir: PathBuf,
+    name: Option<String>,
+    fetcher: Option<Fetcher>,
+}
+
+impl Inputs {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            name: None,
+            fetcher: None,
+        }
+    }
+
+    /// Reads the named input of each day instead, e.g. example1 for <year>/<day>/example1.txt
+    pub fn named(self, name: Option<String>) -> Self {
+        Self { name, ..self }
+    }
+
+    /// Fetches the puzzle inputs that are missing from the directory
+    pub fn fetch_with(self, fetcher: Option<Fetcher>) -> Self {
+        Self { fetcher, ..self }
+    }
+
+    /// The name of the input that's read for each day, if it's not the puzzle input
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The location of the input for a given year and day
+    pub fn path(&self, year: usize, day: usize) -> PathBuf {
+        match &self.name {
+            Some(name) => named_dir(&self.dir, year, day).join(format!("{}.txt", name)),
+            None => self
+                .dir
+                .join(year.to_string())
+                .join(format!("{:02}.txt", day)),
+        }
+    }
+
+    fn fetch(
+        &self,
+        fetcher: &Fetcher,
+        path: &Path,
+        year: usize,
+        day: usize,
+    ) -> Result<String, String> {
+        let input = fetcher.input(year, day)?;
+
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir)
+                .map_err(|error| format!("Could not create {}: {}", dir.display(), error))?;
+        }
+        write(path, &input)
+            .map_err(|error| format!("Could not write {}: {}", path.display(), error))?;
+
+        Ok(input)
+    }
+}
+
+impl Provider for Inputs {
+    fn read(&self, year: usize, day: usize) -> Result<String, String> {
+        let path = self.path(year, day);
+
+        match read_to_string(&path) {
+            Ok(input) => Ok(input),
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                match (&self.name, &self.fetcher) {
+                    (None, Some(fetcher)) => self.fetch(fetcher, &path, year, day),
+                    _ => Err(format!("Missing input {}", path.display())),
+                }
+            }
+            Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+        }
+    }
+}
+
+/// A single input file, used for whichever day is run
+pub struct File(pub PathBuf);
+
+impl Provider for File {
+    fn read(&self, _year: usize, _day: usize) -> Result<String, String> {
+        read_to_string(&self.0)
+            .map_err(|error| format!("Could not read {}: {}", self.0.display(), error))
+    }
+}
+
+/// The input piped into stdin
+pub struct Stdin;
+
+impl Provider for Stdin {
+    fn read(&self, _year: usize, _day: usize) -> Result<String, String> {
+        let mut input = String::new();
+
+        io::stdin()
+            .lock()
+            .read_to_string(&mut input)
+            .map(|_| input)
+            .map_err(|error| format!("Could not read stdin: {}", error))
+    }
+}
+
+/// The directory with the named inputs for a given year and day
+pub fn named_dir(dir: &Path, year: usize, day: usize) -> PathBuf {
+    dir.join(year.to_string()).join(format!("{:02}", day))
+}
+
+/// The names of every named input for a given year and day, in alphabetical order
+pub fn names(dir: &Path, year: usize, day: usize) -> Vec<String> {
+    let entries = match read_dir(named_dir(dir, year, day)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut names = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect::<Vec<String>>();
+
+    names.sort_unstable();
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::fetch::mock;
+
+    #[test]
+    fn test_path() {
+        let inputs = Inputs::new(INPUT_DIR);
+
+        assert_eq!(inputs.path(2020, 1), PathBuf::from("data/2020/01.txt"));
+        assert_eq!(inputs.path(2022, 12), PathBuf::from("data/2022/12.txt"));
+        assert_eq!(
+            Inputs::new("inputs/alice").path(2020, 1),
+            PathBuf::from("inputs/alice/2020/01.txt")
+        );
+    }
+
+    #[test]
+    fn test_named_path() {
+        let inputs = Inputs::new(INPUT_DIR).named(Some(String::from("example1")));
+
+        assert_eq!(
+            inputs.path(2020, 1),
+            PathBuf::from("data/2020/01/example1.txt")
+        );
+    }
+
+    #[test]
+    fn test_fetch_missing_input() {
+        let dir = std::env::temp_dir().join(format!("adventofcode-inputs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let (base_url, server) = mock::serve(vec![(200, "1721\n979\n")]);
+        let inputs =
+            Inputs::new(&dir).fetch_with(Some(Fetcher::new(&base_url, "abc123", Duration::ZERO)));
+
+        assert_eq!(inputs.read(2020, 1), Ok(String::from("1721\n979\n")));
+        assert_eq!(inputs.read(2020, 1), Ok(String::from("1721\n979\n")));
+        assert_eq!(
+            read_to_string(dir.join("2020/01.txt")).unwrap(),
+            "1721\n979\n"
+        );
+        assert_eq!(server.join().unwrap().len(), 1);
+
+        let named = Inputs::new(&dir).named(Some(String::from("example1")));
+        assert!(named
+            .read(2020, 1)
+            .unwrap_err()
+            .starts_with("Missing input"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_names() {
+        let dir = Path::new(INPUT_DIR);
+
+        assert_eq!(
+            names(dir, 2020, 4),
+            vec!["example1", "example2", "example3"]
+        );
+        assert_eq!(names(dir, 2020, 25), Vec::<String>::new());
+    }
+}
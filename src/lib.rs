@@ -0,0 +1,273 @@
+//! Solvers for Advent of Code puzzles, along with everything to run, verify, benchmark and submit
+//! them. The solvers register themselves with `register!`, and are found through the `registry`
+
+use std::any::Any;
+use std::convert::TryFrom;
+
+use serde::{Serialize, Serializer};
+
+pub mod answers;
+pub mod bench;
+pub mod context;
+pub mod error;
+pub mod export;
+pub mod fetch;
+pub mod inputs;
+pub mod output;
+pub mod params;
+pub mod pool;
+pub mod progress;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+// The template is only compiled to check that it builds, as it would otherwise be registered as
+// a day of its own
+#[cfg(test)]
+mod template;
+pub mod utils;
+pub mod visualize;
+pub mod watch;
+pub mod years;
+
+pub use context::{CancellationToken, Context};
+pub use error::{ErrorKind, SolveError};
+pub use params::{Param, Params};
+pub use registry::Puzzle;
+pub use visualize::Frame;
+
+/// The answer to a part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    /// An integer that doesn't fit in an `i64`
+    BigInteger(i128),
+    String(String),
+    /// Multi-line text, e.g. letters rendered by lighting up pixels, one row per line
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Builds a grid answer from rendered text, ignoring trailing whitespace on each row
+    pub fn grid(text: &str) -> Self {
+        Answer::Grid(text.lines().map(|row| row.trim_end().to_string()).collect())
+    }
+
+    /// Whether the answer is the same as an answer written down as text, e.g. a known answer
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Grid(rows) => rows
+                .iter()
+                .map(String::as_str)
+                .eq(expected.trim_matches('\n').lines().map(str::trim_end)),
+            _ => self.to_string() == expected.trim(),
+        }
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(number) => write!(f, "{}", number),
+            Answer::BigInteger(number) => write!(f, "{}", number),
+            Answer::String(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// Integers that fit in an `i64` are serialized as numbers, everything else as text
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(number) => serializer.serialize_i64(*number),
+            answer => serializer.collect_str(answer),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(number: $integer) -> Self {
+                    match i64::try_from(number) {
+                        Ok(number) => Answer::Integer(number),
+                        Err(_) => Answer::BigInteger(number as i128),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, i128);
+
+impl From<u128> for Answer {
+    fn from(number: u128) -> Self {
+        // Anything beyond an i128 is well outside of what a puzzle would ask for
+        Answer::from(number as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::String(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::String(text.to_string())
+    }
+}
+
+/// A solver working directly on the raw input, parsing it in each part. Solvers are shared
+/// between the threads running the parts, hence `Send + Sync`
+pub trait AdventOfCode: Send + Sync {
+    /// The constants of the puzzle that can be overridden, read with `ctx.param`
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn part_one(&self, input: &str, ctx: &Context) -> Result<Answer, SolveError>;
+    fn part_two(&self, input: &str, ctx: &Context) -> Result<Answer, SolveError>;
+
+    /// The frames of the grid a part simulates, for `--visualize`, drawn with
+    /// `visualize::frame`. Empty for parts that don't simulate a grid
+    fn frames(&self, _input: &str, _part: usize, _ctx: &Context) -> Result<Vec<Frame>, SolveError> {
+        Ok(Vec::new())
+    }
+}
+
+/// A solver that parses the input once, and shares the result between both parts
+pub trait Solution: Send + Sync {
+    type Parsed: Send + Sync;
+
+    /// The constants of the puzzle that can be overridden, read with `ctx.param`
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, SolveError>;
+    fn part_one(&self, parsed: &Self::Parsed, ctx: &Context) -> Result<Answer, SolveError>;
+    fn part_two(&self, parsed: &Self::Parsed, ctx: &Context) -> Result<Answer, SolveError>;
+
+    /// The frames of the grid a part simulates, for `--visualize`, drawn with
+    /// `visualize::frame`. Empty for parts that don't simulate a grid
+    fn frames(
+        &self,
+        _parsed: &Self::Parsed,
+        _part: usize,
+        _ctx: &Context,
+    ) -> Result<Vec<Frame>, SolveError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Solvers on the raw input are solutions that parse the input by keeping it as is
+impl<T: AdventOfCode> Solution for T {
+    type Parsed = String;
+
+    fn params(&self) -> &'static [Param] {
+        AdventOfCode::params(self)
+    }
+
+    fn parse(&self, input: &str) -> Result<String, SolveError> {
+        Ok(input.to_string())
+    }
+
+    fn part_one(&self, input: &String, ctx: &Context) -> Result<Answer, SolveError> {
+        AdventOfCode::part_one(self, input, ctx)
+    }
+
+    fn part_two(&self, input: &String, ctx: &Context) -> Result<Answer, SolveError> {
+        AdventOfCode::part_two(self, input, ctx)
+    }
+
+    fn frames(&self, input: &String, part: usize, ctx: &Context) -> Result<Vec<Frame>, SolveError> {
+        AdventOfCode::frames(self, input, part, ctx)
+    }
+}
+
+/// The object safe form of a `Solution`, which is what gets registered and run
+pub trait Solver: Send + Sync {
+    fn params(&self) -> &'static [Param];
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, SolveError>;
+    fn part_one(&self, parsed: &dyn Any, ctx: &Context) -> Result<Answer, SolveError>;
+    fn part_two(&self, parsed: &dyn Any, ctx: &Context) -> Result<Answer, SolveError>;
+    fn frames(
+        &self,
+        parsed: &dyn Any,
+        part: usize,
+        ctx: &Context,
+    ) -> Result<Vec<Frame>, SolveError>;
+}
+
+impl<T> Solver for T
+where
+    T: Solution,
+    T::Parsed: 'static,
+{
+    fn params(&self) -> &'static [Param] {
+        Solution::params(self)
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send + Sync>, SolveError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn part_one(&self, parsed: &dyn Any, ctx: &Context) -> Result<Answer, SolveError> {
+        Solution::part_one(self, downcast::<T>(parsed), ctx)
+    }
+
+    fn part_two(&self, parsed: &dyn Any, ctx: &Context) -> Result<Answer, SolveError> {
+        Solution::part_two(self, downcast::<T>(parsed), ctx)
+    }
+
+    fn frames(
+        &self,
+        parsed: &dyn Any,
+        part: usize,
+        ctx: &Context,
+    ) -> Result<Vec<Frame>, SolveError> {
+        Solution::frames(self, downcast::<T>(parsed), part, ctx)
+    }
+}
+
+fn downcast<T: Solution>(parsed: &dyn Any) -> &T::Parsed
+where
+    T::Parsed: 'static,
+{
+    parsed
+        .downcast_ref::<T::Parsed>()
+        .expect("Parsed input belongs to another solver")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from_integers() {
+        assert_eq!(Answer::from(42usize), Answer::Integer(42));
+        assert_eq!(Answer::from(-7isize), Answer::Integer(-7));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            Answer::BigInteger(i128::from(u64::MAX))
+        );
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+    }
+
+    #[test]
+    fn test_answer_matches() {
+        assert!(Answer::Integer(876459).matches("876459"));
+        assert!(!Answer::Integer(876459).matches("876458"));
+        assert!(Answer::from("abc").matches("abc"));
+
+        let grid = Answer::grid("#..#  \n####\n#..#");
+        assert_eq!(grid.to_string(), "#..#\n####\n#..#");
+        assert!(grid.matches("\n#..#\n####\n#..#\n"));
+        assert!(!grid.matches("#..#\n####"));
+    }
+}
@@ -1,97 +1,634 @@
-use std::fs::File;
 use std::io;
-use std::io::{BufReader, Read};
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
+use structopt::clap::AppSettings;
 use structopt::StructOpt;
 
-mod template;
-mod years;
+use adventofcode::inputs::{self, Provider};
+use adventofcode::{
+    answers, bench, export, fetch, output, params, pool, registry, runner, scaffold, submit,
+    visualize, watch, Frame, Params, Solver,
+};
 
 #[derive(StructOpt)]
+#[structopt(setting = AppSettings::SubcommandsNegateReqs)]
 struct Opt {
-    /// The year to run a solution for. Defaults to the latest year
-    #[structopt(short, long, default_value = "2020")]
-    year: usize,
+    /// The year to run a solution for. Defaults to the latest year, or every day of the year if
+    /// no day is present
+    #[structopt(short, long)]
+    year: Option<usize>,
 
     /// The day to run a solution for
-    #[structopt(short, long)]
-    day: usize,
+    #[structopt(short, long, required_unless_one = &["year", "all"])]
+    day: Option<usize>,
 
     /// The part to run as a numeric value, both if not present
-    #[structopt(short, long)]
+    #[structopt(short, long, possible_values = &["1", "2"])]
     part: Option<usize>,
 
-    /// A relative path to an input file, stdin if not present
+    /// A relative path to an input file. If not present, stdin is read, unless it's a terminal, in
+    /// which case the input is read from data/<year>/<day>.txt
     #[structopt(short, long, parse(from_os_str))]
     file: Option<PathBuf>,
+
+    /// A directory with puzzle inputs laid out as <year>/<day>.txt, e.g. 2020/01.txt, instead of
+    /// data. Used when there's no file, and stdin is a terminal
+    #[structopt(long, parse(from_os_str), conflicts_with = "file")]
+    input_dir: Option<PathBuf>,
+
+    /// The name of an input in data/<year>/<day>/, e.g. example1 for data/2020/01/example1.txt,
+    /// to run instead of the puzzle input
+    #[structopt(short, long, conflicts_with = "file")]
+    input: Option<String>,
+
+    /// The session token of a logged in user, used to fetch puzzle inputs that are missing from
+    /// the input directory
+    #[structopt(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// The site to fetch puzzle inputs from
+    #[structopt(long, env = "AOC_BASE_URL", default_value = fetch::BASE_URL)]
+    base_url: String,
+
+    /// Run every registered solver against its input in data/<year>/<day>.txt
+    #[structopt(short, long, conflicts_with_all = &["year", "day", "file"])]
+    all: bool,
+
+    /// Print the wall time for reading and parsing the input, and for each part
+    #[structopt(short, long)]
+    time: bool,
+
+    /// Benchmark each part by running it the given number of times
+    #[structopt(short, long)]
+    bench: Option<usize>,
+
+    /// Re-run the day whenever its input, its named inputs in data/<year>/<day>/ or its module
+    /// changes, and print how the answers differ from the previous run
+    #[structopt(short, long, requires = "day", conflicts_with_all = &["file", "bench", "format"])]
+    watch: bool,
+
+    /// Override a constant of the day, given as <name>=<value>, e.g. preamble=5. Can be given
+    /// more than once
+    #[structopt(
+        long = "param",
+        requires = "day",
+        number_of_values = 1,
+        parse(try_from_str = params::parse_assignment)
+    )]
+    params: Vec<(String, String)>,
+
+    /// Stop waiting on a part after the given number of seconds, and report it as timed out
+    #[structopt(long)]
+    timeout: Option<f64>,
+
+    /// The number of threads to run days and parts on, one per core if not present
+    #[structopt(short, long)]
+    jobs: Option<usize>,
+
+    /// Replay the grid a part simulates in the terminal, a frame per generation. Space pauses and
+    /// resumes, n and p step to the next and previous frame, and q quits
+    #[structopt(long, requires = "day", conflicts_with_all = &["bench", "watch", "format"])]
+    visualize: bool,
+
+    /// Write the grid a part simulates to an animated GIF if the path ends in .gif, and otherwise
+    /// to a directory with a PNG per frame. With more than one part, each part gets its own file
+    /// or directory, e.g. day11-part2.gif
+    #[structopt(long, requires = "day", conflicts_with_all = &["bench", "watch", "format"])]
+    export: Option<PathBuf>,
+
+    /// How many pixels wide and tall each tile is drawn with --export. 4 if not present
+    #[structopt(long, requires = "export")]
+    cell_size: Option<usize>,
+
+    /// How long to show each frame for with --visualize or --export, in milliseconds. 100 if not
+    /// present
+    #[structopt(long)]
+    frame_delay: Option<u64>,
+
+    /// How to print the answers, either plain, json or csv. Plain if not present
+    #[structopt(long, possible_values = output::FORMATS, conflicts_with = "bench")]
+    format: Option<output::Format>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Create a new day from src/template/day.rs, along with its modules and an empty input file
+    New {
+        /// The year of the new day
+        #[structopt(short, long)]
+        year: usize,
+
+        /// The new day
+        #[structopt(short, long)]
+        day: usize,
+    },
+
+    /// Verify the solvers against the known answers in data/answers.toml, and the named inputs
+    /// against the expected answers in data/<year>/<day>/answers.toml
+    Verify {
+        /// Only verify the given year
+        #[structopt(short, long)]
+        year: Option<usize>,
+
+        /// Only verify the given day
+        #[structopt(short, long)]
+        day: Option<usize>,
+    },
+
+    /// List every day that has a registered solver, along with its puzzle title, tags, URL and
+    /// the approach of the solver
+    List {
+        /// Only list the given year
+        #[structopt(short, long)]
+        year: Option<usize>,
+
+        /// Only list the days tagged with the given tag, e.g. crt or "cellular automaton"
+        #[structopt(short, long)]
+        tag: Option<String>,
+
+        /// List the parameters of the days instead, with their defaults and descriptions, which
+        /// can be overridden with --param
+        #[structopt(long)]
+        params: bool,
+    },
+
+    /// Submit the answer for a part, and record the verdict in data/history.toml. Answers that
+    /// were already rejected, or are outside a known too high or too low bound, are refused
+    Submit {
+        /// The year of the puzzle
+        #[structopt(short, long)]
+        year: usize,
+
+        /// The day of the puzzle
+        #[structopt(short, long)]
+        day: usize,
+
+        /// The part to submit the answer for
+        #[structopt(short, long, possible_values = &["1", "2"])]
+        part: usize,
+
+        /// The answer to submit. If not present, the part is solved for the puzzle input
+        #[structopt(short, long)]
+        answer: Option<String>,
+    },
 }
 
 fn main() {
     let opt = Opt::from_args();
 
-    let part = match opt.part {
-        Some(1) => Some((true, false)),
-        Some(2) => Some((false, true)),
-        None => Some((true, true)),
-        _ => panic!("Invalid part selection"),
+    match &opt.command {
+        Some(Command::New { year, day }) => {
+            match scaffold::scaffold(Path::new(""), *year, *day) {
+                Ok(changed) => changed
+                    .iter()
+                    .for_each(|path| println!("{}", path.display())),
+                Err(error) => fail(&error),
+            }
+
+            return;
+        }
+        Some(Command::Verify { year, day }) => {
+            verify(*year, *day, &input_dir(&opt), &options(&opt), threads(&opt));
+
+            return;
+        }
+        Some(Command::List { year, tag, params }) => {
+            list(*year, tag.as_deref(), *params);
+
+            return;
+        }
+        Some(Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+        }) => {
+            submit(&opt, *year, *day, *part, answer.clone());
+
+            return;
+        }
+        None => {}
+    }
+
+    let inputs = inputs(&opt);
+
+    let parts = match opt.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
     };
 
-    let mut input = String::new();
+    let day = match opt.day {
+        Some(day) => day,
+        None => {
+            let registrations = registry::all()
+                .into_iter()
+                .filter(|registration| opt.all || Some(registration.year) == opt.year);
+
+            if let Some(iterations) = opt.bench {
+                let benchmarks = registrations
+                    .flat_map(|registration| {
+                        let input = inputs.read(registration.year, registration.day);
+                        let day = (registration.build)();
 
-    if let Some(path) = &opt.file {
-        let file = File::open(path).expect("Could not open file.");
+                        parts
+                            .iter()
+                            .map(|&part| bench::Benchmark {
+                                year: registration.year,
+                                day: registration.day,
+                                part,
+                                stats: input.clone().and_then(|input| {
+                                    bench::bench(
+                                        day.as_ref(),
+                                        part,
+                                        &input,
+                                        &Params::default(),
+                                        iterations,
+                                    )
+                                }),
+                            })
+                            .collect::<Vec<bench::Benchmark>>()
+                    })
+                    .collect::<Vec<bench::Benchmark>>();
+
+                bench::print_table(&benchmarks);
+            } else {
+                let registrations = registrations.collect::<Vec<&registry::Registration>>();
+                let outcomes = runner::run_all(
+                    &registrations,
+                    &inputs,
+                    &parts,
+                    &options(&opt),
+                    threads(&opt),
+                );
+
+                match opt.format.unwrap_or(output::Format::Plain) {
+                    output::Format::Plain => runner::print_table(&outcomes),
+                    output::Format::Json => println!("{}", output::to_json(&outcomes)),
+                    output::Format::Csv => print!("{}", output::to_csv(&outcomes)),
+                }
+            }
+
+            return;
+        }
+    };
 
-        BufReader::new(file).read_to_string(&mut input).unwrap();
-    } else {
-        let stdin = io::stdin();
-        let mut guard = stdin.lock();
+    let year = opt.year.unwrap_or(2020);
 
-        guard.read_to_string(&mut input).unwrap();
+    let registration = match registry::find(year, day) {
+        Some(registration) => registration,
+        None => fail(&format!("{}/{} is not implemented", year, day)),
     };
 
-    let normalized_day = format!("{}", opt.day).parse::<usize>().unwrap();
-
-    let day: Box<dyn AdventOfCode> = match (opt.year, normalized_day) {
-        // A template implementation that may be copied to other days. This is the gist of defining
-        // a new day.
-        (42, 42) => Box::new(template::Day {}),
-
-        (2020, 1) => Box::new(years::year2020::Day01 {}),
-        (2020, 2) => Box::new(years::year2020::Day02 {}),
-        (2020, 3) => Box::new(years::year2020::Day03 {}),
-        (2020, 4) => Box::new(years::year2020::Day04 {}),
-        (2020, 5) => Box::new(years::year2020::Day05 {}),
-        (2020, 6) => Box::new(years::year2020::Day06 {}),
-        (2020, 7) => Box::new(years::year2020::Day07 {}),
-        (2020, 8) => Box::new(years::year2020::Day08 {}),
-        (2020, 9) => Box::new(years::year2020::Day09 {}),
-        (2020, 10) => Box::new(years::year2020::Day10 {}),
-        (2020, 11) => Box::new(years::year2020::Day11 {}),
-        (2020, 12) => Box::new(years::year2020::Day12 {}),
-        (2020, 13) => Box::new(years::year2020::Day13 {}),
-        (2020, 14) => Box::new(years::year2020::Day14 {}),
-        (2020, 15) => Box::new(years::year2020::Day15 {}),
-        (2020, 16) => Box::new(years::year2020::Day16 {}),
-
-        (2022, 1) => Box::new(years::year2022::Day01 {}),
-        (2022, 2) => Box::new(years::year2022::Day02 {}),
-
-        _ => panic!("Not implemented"),
+    if opt.watch {
+        fail(&watch::watch(
+            registration,
+            &inputs,
+            &input_dir(&opt),
+            &parts,
+            &options(&opt),
+        ));
+    }
+
+    let day = (registration.build)();
+
+    let start = Instant::now();
+
+    let provider: Box<dyn Provider> = match &opt.file {
+        Some(path) => Box::new(inputs::File(path.clone())),
+        None if opt.input.is_some() || io::stdin().is_terminal() => Box::new(inputs),
+        None => Box::new(inputs::Stdin),
     };
 
-    match part {
-        Some((true, false)) => println!("{}", day.part_one(&input)),
-        Some((false, true)) => println!("{}", day.part_two(&input)),
-        Some((true, true)) => {
-            println!("{}", day.part_one(&input));
-            println!("{}", day.part_two(&input));
+    let input = match provider.read(registration.year, registration.day) {
+        Ok(input) => input,
+        Err(error) if opt.file.is_none() && opt.input.is_none() && opt.session.is_none() => {
+            fail(&format!(
+                "{}. Pass the input with --file, pipe it into stdin, or set AOC_SESSION to fetch it",
+                error
+            ))
         }
-        _ => unreachable!(),
+        Err(error) => fail(&error),
+    };
+
+    if opt.time {
+        eprintln!("input: {:.2?}", start.elapsed());
+    }
+
+    let options = match &opt.input {
+        Some(name) => named_answers(&opt, registration).options(name, &options(&opt)),
+        None => options(&opt),
+    };
+
+    if opt.visualize || opt.export.is_some() {
+        let delay = opt
+            .frame_delay
+            .map_or(visualize::FRAME_DELAY, Duration::from_millis);
+        let frames = record(registration, day.as_ref(), &parts, &input, &options);
+
+        if let Some(path) = &opt.export {
+            let cell_size = opt.cell_size.unwrap_or(export::CELL_SIZE);
+
+            for (part, (label, frames)) in parts.iter().zip(&frames) {
+                let path = match parts.len() {
+                    1 => path.clone(),
+                    _ => export::part_path(path, *part),
+                };
+
+                match export::export(frames, &path, cell_size, delay) {
+                    Ok(_) => eprintln!(
+                        "{}: wrote {} frames to {}",
+                        label,
+                        frames.len(),
+                        path.display()
+                    ),
+                    Err(error) => fail(&format!("{}: {}", label, error)),
+                }
+            }
+        }
+
+        if opt.visualize {
+            let mut player = visualize::Player::new(delay);
+
+            for (label, frames) in &frames {
+                if !player.play(label, frames) {
+                    break;
+                }
+            }
+        }
+
+        return;
+    }
+
+    if let Some(iterations) = opt.bench {
+        let benchmarks = parts
+            .iter()
+            .map(|&part| bench::Benchmark {
+                year: registration.year,
+                day: registration.day,
+                part,
+                stats: bench::bench(day.as_ref(), part, &input, &options.params, iterations),
+            })
+            .collect::<Vec<bench::Benchmark>>();
+
+        bench::print_table(&benchmarks);
+
+        return;
+    }
+
+    let outcomes = runner::run_input(registration, &input, &parts, &options);
+
+    match opt.format.unwrap_or(output::Format::Plain) {
+        output::Format::Plain => {
+            if opt.time {
+                let parse_elapsed = outcomes
+                    .first()
+                    .map(|outcome| outcome.parse_elapsed)
+                    .unwrap_or_default();
+
+                eprintln!("parse: {:.2?}", parse_elapsed);
+            }
+
+            for outcome in &outcomes {
+                match &outcome.answer {
+                    Ok(answer) => println!("{}", answer),
+                    // Every part is printed before exiting, so a failing part doesn't hide the rest
+                    Err(error) => eprintln!("error: part {}: {}", outcome.part, error),
+                }
+
+                if opt.time {
+                    eprintln!("part {}: {:.2?}", outcome.part, outcome.elapsed);
+                }
+            }
+        }
+        output::Format::Json => println!("{}", output::to_json(&outcomes)),
+        output::Format::Csv => print!("{}", output::to_csv(&outcomes)),
+    }
+
+    if outcomes.iter().any(|outcome| outcome.answer.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+/// Records the frames of every part, labelled by the part, before any of them are played or
+/// exported, so a part that fails to record them is reported before the terminal is taken over
+fn record(
+    registration: &registry::Registration,
+    day: &dyn Solver,
+    parts: &[usize],
+    input: &str,
+    options: &runner::Options,
+) -> Vec<(String, Vec<Frame>)> {
+    parts
+        .iter()
+        .map(|&part| {
+            let label = format!(
+                "{}/{:02} part {}",
+                registration.year, registration.day, part
+            );
+
+            match visualize::frames(day, part, input, &options.params) {
+                Ok(frames) if frames.is_empty() => {
+                    fail(&format!("{} has nothing to visualize", label))
+                }
+                Ok(frames) => (label, frames),
+                Err(error) => fail(&format!("{}: {}", label, error)),
+            }
+        })
+        .collect()
+}
+
+fn options(opt: &Opt) -> runner::Options {
+    runner::Options {
+        timeout: opt.timeout.map(Duration::from_secs_f64),
+        progress: io::stderr().is_terminal(),
+        params: opt.params.iter().cloned().collect(),
+    }
+}
+
+/// The expected answers for the named inputs of the day, which hold the parameters to run them
+/// with
+fn named_answers(opt: &Opt, registration: &registry::Registration) -> answers::NamedAnswers {
+    match answers::NamedAnswers::load(&input_dir(opt), registration.year, registration.day) {
+        Ok(answers) => answers,
+        Err(error) => fail(&error),
+    }
+}
+
+fn threads(opt: &Opt) -> usize {
+    opt.jobs.unwrap_or_else(pool::default_threads)
+}
+
+fn input_dir(opt: &Opt) -> PathBuf {
+    opt.input_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(inputs::INPUT_DIR))
+}
+
+/// The inputs in the input directory, fetching the missing ones if there's a session token
+fn inputs(opt: &Opt) -> inputs::Inputs {
+    inputs::Inputs::new(input_dir(opt))
+        .named(opt.input.clone())
+        .fetch_with(fetcher(opt))
+}
+
+fn fetcher(opt: &Opt) -> Option<fetch::Fetcher> {
+    opt.session
+        .as_deref()
+        .map(|session| fetch::Fetcher::new(&opt.base_url, session, fetch::INTERVAL))
+}
+
+/// Reports the error and exits with a non-zero exit code
+fn fail(message: &str) -> ! {
+    eprintln!("error: {}", message);
+
+    std::process::exit(1);
+}
+
+fn verify(
+    year: Option<usize>,
+    day: Option<usize>,
+    dir: &Path,
+    options: &runner::Options,
+    threads: usize,
+) {
+    let answers = match answers::Answers::load(answers::ANSWERS_PATH) {
+        Ok(answers) => answers,
+        Err(error) => fail(&error),
+    };
+
+    let registrations = registry::all()
+        .into_iter()
+        .filter(|registration| year.is_none_or(|year| registration.year == year))
+        .filter(|registration| day.is_none_or(|day| registration.day == day))
+        .collect::<Vec<&registry::Registration>>();
+
+    let verifications = pool::map(threads, &registrations, |registration| {
+        let mut verifications = answers::verify(&answers, dir, registration, options);
+        verifications.extend(answers::verify_named(dir, registration, options));
+
+        verifications
+    })
+    .into_iter()
+    .flatten()
+    .collect::<Vec<answers::Verification>>();
+
+    answers::print_table(&verifications);
+
+    for (year, day) in answers.days() {
+        if registry::find(year, day).is_none() {
+            eprintln!(
+                "{}/{} has known answers, but no registered solver",
+                year, day
+            );
+        }
+    }
+
+    if verifications
+        .iter()
+        .any(|verification| verification.status == answers::Status::Fail)
+    {
+        std::process::exit(1);
+    }
+}
+
+fn submit(opt: &Opt, year: usize, day: usize, part: usize, answer: Option<String>) {
+    let fetcher = match fetcher(opt) {
+        Some(fetcher) => fetcher,
+        None => fail("Set AOC_SESSION to the session token to submit answers"),
+    };
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let registration = match registry::find(year, day) {
+                Some(registration) => registration,
+                None => fail(&format!("{}/{} is not implemented", year, day)),
+            };
+
+            match runner::run(registration, &inputs(opt), &[part], &options(opt))
+                .remove(0)
+                .answer
+            {
+                Ok(answer) => answer.to_string(),
+                Err(error) => fail(&format!("part {}: {}", part, error)),
+            }
+        }
+    };
+
+    let mut history = match submit::History::load(submit::HISTORY_PATH) {
+        Ok(history) => history,
+        Err(error) => fail(&error),
+    };
+
+    let response = submit::submit(&fetcher, &mut history, year, day, part, &answer);
+
+    if let Err(error) = history.save(submit::HISTORY_PATH) {
+        fail(&error);
+    }
+
+    match response {
+        Ok(response @ submit::Response::Verdict(submit::Verdict::Correct)) => {
+            println!("{}: {}", answer, response)
+        }
+        Ok(response) => fail(&format!("{}: {}", answer, response)),
+        Err(error) => fail(&error),
     }
 }
 
-pub trait AdventOfCode {
-    fn part_one(&self, input: &str) -> String;
-    fn part_two(&self, input: &str) -> String;
+fn list(year: Option<usize>, tag: Option<&str>, params: bool) {
+    let registrations = registry::all()
+        .into_iter()
+        .filter(|registration| year.is_none_or(|year| registration.year == year))
+        .filter(|registration| tag.is_none_or(|tag| registration.has_tag(tag)));
+
+    if params {
+        let rows = registrations
+            .flat_map(|registration| {
+                (registration.build)()
+                    .params()
+                    .iter()
+                    .map(|param| {
+                        vec![
+                            registration.year.to_string(),
+                            registration.day.to_string(),
+                            param.name.to_string(),
+                            param.default.to_string(),
+                            param.description.to_string(),
+                        ]
+                    })
+                    .collect::<Vec<Vec<String>>>()
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        runner::print_rows(&["year", "day", "param", "default", "description"], &rows);
+
+        return;
+    }
+
+    let rows = registrations
+        .map(|registration| {
+            vec![
+                registration.year.to_string(),
+                registration.day.to_string(),
+                registration.puzzle.title.to_string(),
+                registration.puzzle.tags.join(", "),
+                registration.url(),
+                registration
+                    .puzzle
+                    .description
+                    .unwrap_or_default()
+                    .to_string(),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    runner::print_rows(
+        &["year", "day", "title", "tags", "url", "description"],
+        &rows,
+    );
 }
@@ -0,0 +1,153 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::runner::Outcome;
+use crate::Answer;
+
+/// The names of the formats, as accepted on the command line
+pub const FORMATS: &[&str] = &["plain", "json", "csv"];
+
+/// How the outcomes of a run are printed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    /// Bare answers for a single day, and a table for multiple days
+    Plain,
+    /// A JSON array with a record for every part
+    Json,
+    /// A CSV file with a header, and a record for every part
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format {:?}", format)),
+        }
+    }
+}
+
+/// The outcome of a single part, as it's written in the machine-readable formats
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record<'a> {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: Option<&'a Answer>,
+    pub duration_ms: f64,
+    pub error: Option<&'a str>,
+}
+
+impl<'a> From<&'a Outcome> for Record<'a> {
+    fn from(outcome: &'a Outcome) -> Self {
+        Record {
+            year: outcome.year,
+            day: outcome.day,
+            part: outcome.part,
+            answer: outcome.answer.as_ref().ok(),
+            // Microsecond precision is plenty, and keeps floating point noise out of the output
+            duration_ms: outcome.elapsed.as_micros() as f64 / 1000.0,
+            error: outcome.answer.as_ref().err().map(String::as_str),
+        }
+    }
+}
+
+pub fn to_json(outcomes: &[Outcome]) -> String {
+    let records = outcomes.iter().map(Record::from).collect::<Vec<Record>>();
+
+    serde_json::to_string_pretty(&records).expect("Records are always serializable")
+}
+
+pub fn to_csv(outcomes: &[Outcome]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    for outcome in outcomes {
+        writer
+            .serialize(Record::from(outcome))
+            .expect("Records are always serializable");
+    }
+
+    let csv = writer.into_inner().expect("Writing to memory never fails");
+
+    String::from_utf8(csv).expect("Records are valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn outcomes() -> Vec<Outcome> {
+        vec![
+            Outcome {
+                year: 2020,
+                day: 1,
+                part: 1,
+                answer: Ok(Answer::Integer(876459)),
+                parse_elapsed: Duration::default(),
+                elapsed: Duration::from_micros(1500),
+            },
+            Outcome {
+                year: 2020,
+                day: 1,
+                part: 2,
+                answer: Err(String::from("no solution, no matches")),
+                parse_elapsed: Duration::default(),
+                elapsed: Duration::from_millis(2),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&outcomes())).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "year": 2020,
+                    "day": 1,
+                    "part": 1,
+                    "answer": 876459,
+                    "duration_ms": 1.5,
+                    "error": null
+                },
+                {
+                    "year": 2020,
+                    "day": 1,
+                    "part": 2,
+                    "answer": null,
+                    "duration_ms": 2.0,
+                    "error": "no solution, no matches"
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&outcomes()),
+            "year,day,part,answer,duration_ms,error
+2020,1,1,876459,1.5,
+2020,1,2,,2.0,\"no solution, no matches\"
+"
+        );
+    }
+
+    #[test]
+    fn test_grid_answers_are_quoted() {
+        let mut outcomes = outcomes();
+        outcomes[0].answer = Ok(Answer::grid("#.\n.#"));
+
+        assert!(to_csv(&outcomes).contains(",\"#.\n.#\","));
+        assert!(to_json(&outcomes).contains("\"answer\": \"#.\\n.#\""));
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+/// A constant of a puzzle that can be overridden from the CLI with `--param <name>=<value>`, e.g.
+/// the size of the preamble, which differs between the examples and the puzzle input
+pub struct Param {
+    pub name: &'static str,
+    /// The value used unless it's overridden, parsed the same way as an overriding value
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// The values overriding the defaults of the parameters of a day, keyed by name
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// The overriding value of the parameter, or its default
+    pub fn get(&self, param: &Param) -> &str {
+        self.0
+            .get(param.name)
+            .map(String::as_str)
+            .unwrap_or(param.default)
+    }
+
+    /// Overrides the values with the given ones
+    pub fn overridden_by(mut self, params: &Params) -> Self {
+        self.0.extend(params.0.clone());
+
+        self
+    }
+
+    /// Fails if a value is given for a parameter the day doesn't have, listing the ones it has
+    pub fn check(&self, declared: &[Param]) -> Result<(), String> {
+        let unknown = match self
+            .0
+            .keys()
+            .find(|name| declared.iter().all(|param| param.name != name.as_str()))
+        {
+            Some(unknown) => unknown,
+            None => return Ok(()),
+        };
+
+        if declared.is_empty() {
+            return Err(format!("unknown parameter {}, there are none", unknown));
+        }
+
+        let known = declared
+            .iter()
+            .map(|param| format!("{} (default {})", param.name, param.default))
+            .collect::<Vec<String>>();
+
+        Err(format!(
+            "unknown parameter {}, expected one of {}",
+            unknown,
+            known.join(", ")
+        ))
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(params: I) -> Self {
+        Self(params.into_iter().collect())
+    }
+}
+
+/// Parses a parameter given as <name>=<value>
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), String> {
+    match assignment.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "Invalid parameter {:?}, expected <name>=<value>",
+            assignment
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREAMBLE: Param = Param {
+        name: "preamble",
+        default: "25",
+        description: "The number of preceding numbers a number is checked against",
+    };
+
+    #[test]
+    fn test_get() {
+        let params = vec![(String::from("preamble"), String::from("5"))]
+            .into_iter()
+            .collect::<Params>();
+
+        assert_eq!(Params::default().get(&PREAMBLE), "25");
+        assert_eq!(params.get(&PREAMBLE), "5");
+        assert_eq!(Params::default().overridden_by(&params), params);
+    }
+
+    #[test]
+    fn test_check() {
+        let params = vec![(String::from("preambel"), String::from("5"))]
+            .into_iter()
+            .collect::<Params>();
+
+        assert_eq!(Params::default().check(&[]), Ok(()));
+        assert_eq!(
+            params.check(&[PREAMBLE]),
+            Err(String::from(
+                "unknown parameter preambel, expected one of preamble (default 25)"
+            ))
+        );
+        assert_eq!(
+            params.check(&[]),
+            Err(String::from("unknown parameter preambel, there are none"))
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("preamble=5"),
+            Ok((String::from("preamble"), String::from("5")))
+        );
+        assert_eq!(
+            parse_assignment("bag=shiny gold"),
+            Ok((String::from("bag"), String::from("shiny gold")))
+        );
+        assert!(parse_assignment("preamble").is_err());
+        assert!(parse_assignment("=5").is_err());
+    }
+}
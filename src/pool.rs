@@ -0,0 +1,68 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// The number of threads to use when none is configured, one per available core
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+}
+
+/// Maps every item on up to the given number of threads, each taking the next item as soon as
+/// it's done with the previous one. The results are in the order of the items, no matter which
+/// thread finishes first
+pub fn map<T, R, F>(threads: usize, items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = items
+        .iter()
+        .map(|_| Mutex::new(None))
+        .collect::<Vec<Mutex<Option<R>>>>();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                let result = f(item);
+                *results[index].lock().unwrap() = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.into_inner().unwrap().expect("Every item is mapped"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items = (0..20).collect::<Vec<u64>>();
+
+        let results = map(4, &items, |&item| {
+            // The first items are the slowest, so they finish last
+            thread::sleep(Duration::from_millis(20 - item));
+
+            item * 2
+        });
+
+        assert_eq!(results, (0..20).map(|item| item * 2).collect::<Vec<u64>>());
+        assert_eq!(map(4, &[] as &[u64], |&item| item), Vec::<u64>::new());
+    }
+}
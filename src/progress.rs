@@ -0,0 +1,93 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::context::{Event, Reporter};
+
+/// The number of characters filled in by a complete bar
+const WIDTH: usize = 30;
+
+/// Marks a bar that hasn't been drawn yet
+const UNDRAWN: usize = usize::MAX;
+
+/// Renders the progress of a part as a bar on the last line of stderr, meant for terminals. The
+/// bar is only redrawn when its percentage changes, and is cleared once the part is done
+pub struct Bar {
+    label: String,
+    percent: AtomicUsize,
+}
+
+impl Bar {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            percent: AtomicUsize::new(UNDRAWN),
+        }
+    }
+}
+
+impl Reporter for Bar {
+    fn report(&self, event: Event) {
+        let mut stderr = io::stderr().lock();
+
+        // Writing to stderr is best effort, as there's nowhere left to report failures
+        let _ = match event {
+            Event::Progress { current, total } => {
+                let percent = (current.min(total) * 100).checked_div(total).unwrap_or(100);
+
+                if self.percent.swap(percent, Ordering::Relaxed) == percent {
+                    return;
+                }
+
+                write!(stderr, "\r\x1b[K{}", render(&self.label, percent))
+            }
+            Event::Log(message) => {
+                // The log line replaces the bar, which is drawn again on the next progress
+                self.percent.store(UNDRAWN, Ordering::Relaxed);
+
+                writeln!(stderr, "\r\x1b[K{}: {}", self.label, message)
+            }
+        };
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        if *self.percent.get_mut() != UNDRAWN {
+            let _ = write!(io::stderr(), "\r\x1b[K");
+        }
+    }
+}
+
+fn render(label: &str, percent: usize) -> String {
+    let filled = percent * WIDTH / 100;
+
+    format!(
+        "{} [{}{}] {:>3}%",
+        label,
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        percent
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render("2020/15 part 2", 0),
+            "2020/15 part 2 [                              ]   0%"
+        );
+        assert_eq!(
+            render("2020/15 part 2", 50),
+            "2020/15 part 2 [###############               ]  50%"
+        );
+        assert_eq!(
+            render("2020/15 part 2", 100),
+            "2020/15 part 2 [##############################] 100%"
+        );
+    }
+}
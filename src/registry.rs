@@ -0,0 +1,130 @@
+use crate::{fetch, Solver};
+
+/// What the puzzle of a day is, and how its solver goes about it, to help find prior art when a
+/// similar puzzle comes up
+pub struct Puzzle {
+    pub title: &'static str,
+    /// The algorithms and techniques the solver relies on, e.g. "CRT", "cellular automaton" or
+    /// "DP"
+    pub tags: &'static [&'static str],
+    /// The approach of the solver, when it's not obvious from the title and tags
+    pub description: Option<&'static str>,
+}
+
+/// A solver for a single day, submitted to the registry with the `register!` macro
+pub struct Registration {
+    pub year: usize,
+    pub day: usize,
+    pub puzzle: Puzzle,
+    pub build: fn() -> Box<dyn Solver>,
+}
+
+impl Registration {
+    /// The page with the description of the puzzle
+    pub fn url(&self) -> String {
+        format!("{}/{}/day/{}", fetch::BASE_URL, self.year, self.day)
+    }
+
+    /// Whether the solver is tagged with the tag, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.puzzle
+            .tags
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(tag))
+    }
+}
+
+inventory::collect!(Registration);
+
+/// Registers a solver for the given year and day along with its puzzle, making it available to
+/// the CLI.
+///
+/// ```ignore
+/// register!(
+///     2020,
+///     1,
+///     Day01 {},
+///     Puzzle {
+///         title: "Report Repair",
+///         tags: &["brute force"],
+///         description: None,
+///     }
+/// );
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($year:expr, $day:expr, $solver:expr, $puzzle:expr) => {
+        inventory::submit! {
+            $crate::registry::Registration {
+                year: $year,
+                day: $day,
+                puzzle: $puzzle,
+                build: || Box::new($solver),
+            }
+        }
+    };
+}
+
+/// Finds the solver registered for the given year and day
+pub fn find(year: usize, day: usize) -> Option<&'static Registration> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .find(|registration| registration.year == year && registration.day == day)
+}
+
+/// Every registered solver, ordered by year and day
+pub fn all() -> Vec<&'static Registration> {
+    let mut registrations = inventory::iter::<Registration>
+        .into_iter()
+        .collect::<Vec<&Registration>>();
+
+    registrations.sort_unstable_by_key(|registration| (registration.year, registration.day));
+
+    registrations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrations_are_unique() {
+        let registrations = all();
+
+        registrations.windows(2).for_each(|pair| {
+            assert!(
+                (pair[0].year, pair[0].day) != (pair[1].year, pair[1].day),
+                "{}/{} is registered more than once",
+                pair[0].year,
+                pair[0].day
+            );
+        });
+    }
+
+    #[test]
+    fn test_find_registered_day() {
+        assert!(find(2020, 1).is_some());
+        assert!(find(2020, 16).is_some());
+        assert!(find(2022, 2).is_some());
+        assert!(find(2020, 26).is_none());
+    }
+
+    #[test]
+    fn test_puzzles() {
+        let registration = find(2020, 13).unwrap();
+
+        assert_eq!(registration.puzzle.title, "Shuttle Search");
+        assert_eq!(registration.url(), "https://adventofcode.com/2020/day/13");
+        assert!(registration.has_tag("crt"));
+        assert!(!registration.has_tag("dp"));
+
+        all().iter().for_each(|registration| {
+            assert!(
+                !registration.puzzle.title.is_empty(),
+                "{}/{} has no title",
+                registration.year,
+                registration.day
+            );
+        });
+    }
+}
@@ -0,0 +1,444 @@
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::inputs::Provider;
+use crate::registry::Registration;
+use crate::{pool, progress};
+use crate::{Answer, Context, Params, Solver};
+
+/// The result of running a single part of a registered solver
+pub struct Outcome {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: Result<Answer, String>,
+    /// The time spent parsing the input, shared by every part of the day
+    pub parse_elapsed: Duration,
+    pub elapsed: Duration,
+}
+
+/// How the parts are run
+#[derive(Clone, Default)]
+pub struct Options {
+    /// How long to wait on a part before cancelling it, and reporting it as timed out
+    pub timeout: Option<Duration>,
+    /// Whether to render the progress reported by the parts on stderr
+    pub progress: bool,
+    /// The values overriding the defaults of the parameters of the days
+    pub params: Params,
+}
+
+/// A solver along with its parsed input, or the reason it couldn't be read or parsed
+struct Day {
+    solver: Arc<dyn Solver>,
+    parsed: Result<(Arc<dyn Any + Send + Sync>, Duration), String>,
+}
+
+impl Day {
+    fn parse(registration: &Registration, input: Result<String, String>) -> Self {
+        let solver: Arc<dyn Solver> = Arc::from((registration.build)());
+        let parsed = input.and_then(|input| {
+            let start = Instant::now();
+            let parsed = parse(solver.as_ref(), &input)?;
+
+            Ok((Arc::from(parsed), start.elapsed()))
+        });
+
+        Self { solver, parsed }
+    }
+
+    fn solve(&self, registration: &Registration, part: usize, options: &Options) -> Outcome {
+        let start = Instant::now();
+        let (answer, parse_elapsed) = match &self.parsed {
+            Ok((parsed, parse_elapsed)) => {
+                let answer = options.params.check(self.solver.params()).and_then(|_| {
+                    let ctx = context(registration, part, options);
+
+                    solve_within(&self.solver, part, parsed, ctx, options.timeout)
+                });
+
+                (answer, *parse_elapsed)
+            }
+            Err(error) => (Err(error.clone()), Duration::default()),
+        };
+
+        Outcome {
+            year: registration.year,
+            day: registration.day,
+            part,
+            answer,
+            parse_elapsed,
+            elapsed: start.elapsed(),
+        }
+    }
+}
+
+/// The context to solve a part within, with the parameters, and a progress bar if requested
+fn context(registration: &Registration, part: usize, options: &Options) -> Context {
+    let ctx = Context::default().with_params(options.params.clone());
+
+    if !options.progress {
+        return ctx;
+    }
+
+    let label = format!(
+        "{}/{:02} part {}",
+        registration.year, registration.day, part
+    );
+
+    ctx.reporting_to(Arc::new(progress::Bar::new(label)))
+}
+
+/// Runs the given parts of a registered solver against its input from the provider
+pub fn run(
+    registration: &Registration,
+    provider: &dyn Provider,
+    parts: &[usize],
+    options: &Options,
+) -> Vec<Outcome> {
+    run_all(&[registration], provider, parts, options, 1)
+}
+
+/// Runs the given parts of a registered solver against the given input
+pub fn run_input(
+    registration: &Registration,
+    input: &str,
+    parts: &[usize],
+    options: &Options,
+) -> Vec<Outcome> {
+    let day = Day::parse(registration, Ok(input.to_string()));
+
+    parts
+        .iter()
+        .map(|&part| day.solve(registration, part, options))
+        .collect()
+}
+
+/// Runs the given parts of every registered solver against its input from the provider, on up to
+/// the given number of threads. Each day is parsed once, after which its parts are independent
+/// jobs, so the outcomes come in the order of the registrations and parts regardless of which
+/// finishes first
+pub fn run_all(
+    registrations: &[&Registration],
+    provider: &dyn Provider,
+    parts: &[usize],
+    options: &Options,
+    threads: usize,
+) -> Vec<Outcome> {
+    let days = pool::map(threads, registrations, |registration| {
+        Day::parse(
+            registration,
+            provider.read(registration.year, registration.day),
+        )
+    });
+
+    let jobs = (0..registrations.len())
+        .flat_map(|index| parts.iter().map(move |&part| (index, part)))
+        .collect::<Vec<(usize, usize)>>();
+
+    pool::map(threads, &jobs, |&(index, part)| {
+        days[index].solve(registrations[index], part, options)
+    })
+}
+
+/// Parses the input for a solver, turning a failing or panicking parser into an error
+pub fn parse(day: &dyn Solver, input: &str) -> Result<Box<dyn Any + Send + Sync>, String> {
+    catch_unwind(AssertUnwindSafe(|| day.parse(input)))
+        .map_err(|panic| panic_message(panic.as_ref()))?
+        .map_err(|error| error.to_string())
+}
+
+/// Solves a single part on the parsed input, turning a failing or panicking solver into an error
+/// instead of aborting the run
+pub fn solve(
+    day: &dyn Solver,
+    part: usize,
+    parsed: &dyn Any,
+    ctx: &Context,
+) -> Result<Answer, String> {
+    if !(1..=2).contains(&part) {
+        return Err(format!("There is no part {}", part));
+    }
+
+    catch_unwind(AssertUnwindSafe(|| match part {
+        1 => day.part_one(parsed, ctx),
+        _ => day.part_two(parsed, ctx),
+    }))
+    .map_err(|panic| panic_message(panic.as_ref()))?
+    .map_err(|error| error.to_string())
+}
+
+/// Solves a single part, giving up on it once the timeout has passed. The part is solved on a
+/// thread of its own, which is cancelled, but left to stop by itself, since threads can't be
+/// stopped from the outside
+fn solve_within(
+    day: &Arc<dyn Solver>,
+    part: usize,
+    parsed: &Arc<dyn Any + Send + Sync>,
+    ctx: Context,
+    timeout: Option<Duration>,
+) -> Result<Answer, String> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return solve(day.as_ref(), part, parsed.as_ref(), &ctx),
+    };
+
+    let token = ctx.token().clone();
+    let (day, parsed) = (Arc::clone(day), Arc::clone(parsed));
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let _ = sender.send(solve(day.as_ref(), part, parsed.as_ref(), &ctx));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(answer) => answer,
+        Err(_) => {
+            token.cancel();
+
+            Err(format!("timed out after {:.2?}", timeout))
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("Solver panicked")
+    }
+}
+
+/// Prints a summary table with a row for every outcome
+pub fn print_table(outcomes: &[Outcome]) {
+    let rows = outcomes
+        .iter()
+        .map(|outcome| {
+            vec![
+                outcome.year.to_string(),
+                outcome.day.to_string(),
+                outcome.part.to_string(),
+                match &outcome.answer {
+                    Ok(answer) => answer.to_string(),
+                    Err(error) => format!("error: {}", error),
+                },
+                format!("{:.2?}", outcome.parse_elapsed),
+                format!("{:.2?}", outcome.elapsed),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    print_rows(
+        &["year", "day", "part", "answer", "parse", "elapsed"],
+        &rows,
+    );
+}
+
+/// Prints rows as columns aligned to the widest cell in each column
+pub fn print_rows(header: &[&str], rows: &[Vec<String>]) {
+    let header = header
+        .iter()
+        .map(|&cell| String::from(cell))
+        .collect::<Vec<String>>();
+
+    let widths = std::iter::once(&header).chain(rows.iter()).fold(
+        vec![0; header.len()],
+        |mut widths, row| {
+            row.iter().enumerate().for_each(|(column, cell)| {
+                widths[column] = std::cmp::max(widths[column], cell.chars().count());
+            });
+
+            widths
+        },
+    );
+
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ");
+
+        println!("{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::{Inputs, INPUT_DIR};
+    use crate::{registry, AdventOfCode, CancellationToken, SolveError};
+
+    #[test]
+    fn test_run_registered_day() {
+        let registration = registry::find(2020, 1).expect("2020 day 1 is not registered");
+        let outcomes = run(
+            registration,
+            &Inputs::new(INPUT_DIR),
+            &[1, 2],
+            &Options::default(),
+        );
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].part, 1);
+        assert_eq!(outcomes[0].answer, Ok(Answer::Integer(876459)));
+        assert_eq!(outcomes[1].part, 2);
+        assert_eq!(outcomes[1].answer, Ok(Answer::Integer(116168640)));
+
+        let outcomes = run(
+            registration,
+            &Inputs::new(INPUT_DIR),
+            &[3],
+            &Options::default(),
+        );
+        assert_eq!(outcomes[0].answer, Err(String::from("There is no part 3")));
+    }
+
+    #[test]
+    fn test_run_with_params() {
+        let registration = registry::find(2020, 9).expect("2020 day 9 is not registered");
+        let input = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127";
+        let options = |name: &str| Options {
+            params: vec![(name.to_string(), String::from("5"))]
+                .into_iter()
+                .collect(),
+            ..Options::default()
+        };
+
+        let outcomes = run_input(registration, input, &[1], &options("preamble"));
+        assert_eq!(outcomes[0].answer, Ok(Answer::Integer(127)));
+
+        let outcomes = run_input(registration, input, &[1], &options("size"));
+        assert!(outcomes[0]
+            .answer
+            .as_ref()
+            .unwrap_err()
+            .starts_with("unknown parameter size"));
+    }
+
+    #[test]
+    fn test_run_all_in_order() {
+        let registrations = registry::all()
+            .into_iter()
+            .filter(|registration| registration.year == 2020)
+            .collect::<Vec<&Registration>>();
+        let outcomes = run_all(
+            &registrations,
+            &Inputs::new(INPUT_DIR),
+            &[1, 2],
+            &Options::default(),
+            4,
+        );
+
+        assert_eq!(outcomes.len(), registrations.len() * 2);
+
+        for (outcome, (registration, part)) in outcomes.iter().zip(
+            registrations
+                .iter()
+                .flat_map(|registration| vec![(registration, 1), (registration, 2)]),
+        ) {
+            assert_eq!(
+                (outcome.year, outcome.day, outcome.part),
+                (registration.year, registration.day, part)
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_reports_errors() {
+        let day = (registry::find(2020, 1).unwrap().build)();
+        let parsed = parse(day.as_ref(), "220\n1010").unwrap();
+
+        assert_eq!(
+            solve(day.as_ref(), 1, parsed.as_ref(), &Context::default()),
+            Err(String::from("no solution, no matches"))
+        );
+        assert_eq!(
+            parse(day.as_ref(), "220\nabc").unwrap_err(),
+            "line 2: not a number \"abc\""
+        );
+    }
+
+    struct Lines;
+
+    impl AdventOfCode for Lines {
+        fn part_one(&self, input: &str, _ctx: &Context) -> Result<Answer, SolveError> {
+            Ok(input.lines().count().into())
+        }
+
+        fn part_two(&self, _input: &str, _ctx: &Context) -> Result<Answer, SolveError> {
+            panic!("Out of bounds")
+        }
+    }
+
+    /// Spins in part one until it's cancelled, while part two answers right away
+    struct Spin;
+
+    impl AdventOfCode for Spin {
+        fn part_one(&self, _input: &str, ctx: &Context) -> Result<Answer, SolveError> {
+            loop {
+                ctx.check()?;
+                thread::yield_now();
+            }
+        }
+
+        fn part_two(&self, _input: &str, _ctx: &Context) -> Result<Answer, SolveError> {
+            Ok(Answer::Integer(2))
+        }
+    }
+
+    #[test]
+    fn test_solve_raw_input() {
+        let parsed = parse(&Lines, "a\nb\nc").unwrap();
+
+        assert_eq!(
+            solve(&Lines, 1, parsed.as_ref(), &Context::default()),
+            Ok(Answer::Integer(3))
+        );
+    }
+
+    #[test]
+    fn test_solve_catches_panics() {
+        let parsed = parse(&Lines, "").unwrap();
+
+        assert_eq!(
+            solve(&Lines, 2, parsed.as_ref(), &Context::default()),
+            Err(String::from("Out of bounds"))
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let day: Arc<dyn Solver> = Arc::new(Spin);
+        let parsed: Arc<dyn Any + Send + Sync> = Arc::from(parse(day.as_ref(), "").unwrap());
+        let timeout = Some(Duration::from_millis(50));
+
+        assert_eq!(
+            solve_within(&day, 1, &parsed, Context::default(), timeout),
+            Err(String::from("timed out after 50.00ms"))
+        );
+        assert_eq!(
+            solve_within(&day, 2, &parsed, Context::default(), timeout),
+            Ok(Answer::Integer(2))
+        );
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let token = CancellationToken::default();
+        token.cancel();
+
+        let parsed = parse(&Spin, "").unwrap();
+
+        assert_eq!(
+            solve(&Spin, 1, parsed.as_ref(), &Context::new(token)),
+            Err(String::from("cancelled"))
+        );
+    }
+}
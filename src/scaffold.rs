@@ -0,0 +1,272 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::inputs::{self, NAMED_ANSWERS};
+
+/// The template new days are created from, relative to the root of the repository
+pub const TEMPLATE_PATH: &str = "src/template/day.rs";
+
+/// The answers of a new day's example, which verify checks once they're filled in
+const EXAMPLE_ANSWERS: &str = "[example1]
+# part_one = \"\"
+# part_two = \"\"
+";
+
+/// Creates a new day from the template, declares its modules, and creates an empty input file,
+/// along with an empty example and its answers to fill in from the puzzle description. Returns
+/// every file that was created or changed.
+pub fn scaffold(root: &Path, year: usize, day: usize) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There's no day {} in Advent of Code", day));
+    }
+
+    let years_path = root.join("src/years");
+    let day_path = day_path(root, year, day);
+    let year_path = day_path
+        .parent()
+        .expect("Days are in a year module")
+        .to_path_buf();
+
+    if day_path.exists() {
+        return Err(format!("{} already exists", day_path.display()));
+    }
+
+    let template_path = root.join(TEMPLATE_PATH);
+    let template = fs::read_to_string(&template_path)
+        .map_err(|error| format!("Could not read {}: {}", template_path.display(), error))?;
+
+    let mut changed = Vec::new();
+
+    fs::create_dir_all(&year_path).map_err(|error| write_error(&year_path, error))?;
+    write(&day_path, &from_template(&template, year, day))?;
+    changed.push(day_path);
+
+    let year_mod_path = year_path.join("mod.rs");
+    if declare_module(&year_mod_path, &format!("pub mod day{:02};", day))? {
+        changed.push(year_mod_path);
+    }
+
+    let years_mod_path = years_path.join("mod.rs");
+    if declare_module(&years_mod_path, &format!("pub mod year{};", year))? {
+        changed.push(years_mod_path);
+    }
+
+    let input_path = root.join(format!("data/{}/{:02}.txt", year, day));
+    if !input_path.exists() {
+        let input_dir = input_path.parent().expect("Input files are in a directory");
+
+        fs::create_dir_all(input_dir).map_err(|error| write_error(input_dir, error))?;
+        write(&input_path, "")?;
+        changed.push(input_path);
+    }
+
+    let named_dir = inputs::named_dir(&root.join(inputs::INPUT_DIR), year, day);
+    fs::create_dir_all(&named_dir).map_err(|error| write_error(&named_dir, error))?;
+
+    for (name, content) in [("example1.txt", ""), (NAMED_ANSWERS, EXAMPLE_ANSWERS)] {
+        let path = named_dir.join(name);
+
+        if !path.exists() {
+            write(&path, content)?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// The conventional location of the module for a given year and day
+pub fn day_path(root: &Path, year: usize, day: usize) -> PathBuf {
+    root.join("src/years")
+        .join(format!("year{}", year))
+        .join(format!("day{:02}.rs", day))
+}
+
+/// Renames the template struct, and registers it for the given day
+fn from_template(template: &str, year: usize, day: usize) -> String {
+    let name = format!("Day{:02}", day);
+
+    replace_word(template, "Day", &name).replace(
+        "register!(\n    42,\n    42,",
+        &format!("register!(\n    {},\n    {},", year, day),
+    )
+}
+
+/// Replaces every occurrence of a word that isn't part of a longer identifier
+fn replace_word(source: &str, word: &str, replacement: &str) -> String {
+    let is_identifier = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some(index) = rest.find(word) {
+        let before = rest[..index]
+            .chars()
+            .last()
+            .or_else(|| result.chars().last());
+        let after = rest[index + word.len()..].chars().next();
+
+        result.push_str(&rest[..index]);
+
+        if is_identifier(before) || is_identifier(after) {
+            result.push_str(word);
+        } else {
+            result.push_str(replacement);
+        }
+
+        rest = &rest[index + word.len()..];
+    }
+
+    result.push_str(rest);
+
+    result
+}
+
+/// Adds a module declaration in order, unless it's already declared. Returns whether the file changed.
+fn declare_module(path: &Path, declaration: &str) -> Result<bool, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(format!("Could not read {}: {}", path.display(), error)),
+    };
+
+    let mut lines = content.lines().collect::<Vec<&str>>();
+
+    if lines.contains(&declaration) {
+        return Ok(false);
+    }
+
+    let position = lines
+        .iter()
+        .position(|&line| line.starts_with("mod ") || line.starts_with("pub mod "))
+        .map(|first| {
+            first
+                + lines[first..]
+                    .iter()
+                    .take_while(|&&line| line < declaration && !line.is_empty())
+                    .count()
+        })
+        .unwrap_or(lines.len());
+
+    lines.insert(position, declaration);
+
+    write(path, &format!("{}\n", lines.join("\n")))?;
+
+    Ok(true)
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|error| write_error(path, error))
+}
+
+fn write_error(path: &Path, error: std::io::Error) -> String {
+    format!("Could not write {}: {}", path.display(), error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::NamedAnswers;
+
+    /// A scratch copy of the parts of the repository that scaffolding touches
+    fn scratch(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "adventofcode-scaffold-{}-{}",
+            name,
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/template")).unwrap();
+        fs::create_dir_all(root.join("src/years/year2020")).unwrap();
+        fs::copy(TEMPLATE_PATH, root.join(TEMPLATE_PATH)).unwrap();
+        fs::write(root.join("src/years/mod.rs"), "pub mod year2020;\n").unwrap();
+        fs::write(
+            root.join("src/years/year2020/mod.rs"),
+            "pub mod day01;\npub mod day05;\n",
+        )
+        .unwrap();
+
+        root
+    }
+
+    #[test]
+    fn test_replace_word() {
+        assert_eq!(
+            replace_word(
+                "pub struct Day {}\nimpl Solution for Day {\nDays Today Day",
+                "Day",
+                "Day03"
+            ),
+            "pub struct Day03 {}\nimpl Solution for Day03 {\nDays Today Day03"
+        );
+    }
+
+    #[test]
+    fn test_from_template() {
+        let template = fs::read_to_string(TEMPLATE_PATH).unwrap();
+        let source = from_template(&template, 2022, 3);
+
+        assert!(source.contains("pub struct Day03 {}"));
+        assert!(source.contains("impl Solution for Day03 {"));
+        assert!(source.contains("register!(\n    2022,\n    3,\n    Day03 {},"));
+        assert!(!source.contains("#[ignore"));
+    }
+
+    #[test]
+    fn test_scaffold_new_year() {
+        let root = scratch("new-year");
+
+        let changed = scaffold(&root, 2022, 3).unwrap();
+
+        assert_eq!(
+            changed,
+            vec![
+                root.join("src/years/year2022/day03.rs"),
+                root.join("src/years/year2022/mod.rs"),
+                root.join("src/years/mod.rs"),
+                root.join("data/2022/03.txt"),
+                root.join("data/2022/03/example1.txt"),
+                root.join("data/2022/03/answers.toml"),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/years/year2022/mod.rs")).unwrap(),
+            "pub mod day03;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/years/mod.rs")).unwrap(),
+            "pub mod year2020;\npub mod year2022;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("data/2022/03.txt")).unwrap(),
+            ""
+        );
+
+        // The example isn't checked until its answers are filled in
+        let answers = NamedAnswers::load(&root.join("data"), 2022, 3).unwrap();
+        assert_eq!(answers.get("example1", 1), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_existing_year() {
+        let root = scratch("existing-year");
+
+        scaffold(&root, 2020, 3).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.join("src/years/year2020/mod.rs")).unwrap(),
+            "pub mod day01;\npub mod day03;\npub mod day05;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/years/mod.rs")).unwrap(),
+            "pub mod year2020;\n"
+        );
+        assert!(scaffold(&root, 2020, 3).is_err());
+        assert!(scaffold(&root, 2020, 26).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
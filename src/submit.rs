@@ -0,0 +1,418 @@
+use std::fmt;
+use std::fs::{read_to_string, write};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::fetch::Fetcher;
+
+/// The conventional location of the history of submitted answers
+pub const HISTORY_PATH: &str = "data/history.toml";
+
+/// The verdict on a submitted answer
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+        }
+    }
+}
+
+/// The response to a submitted answer
+#[derive(Debug, PartialEq)]
+pub enum Response {
+    Verdict(Verdict),
+    /// An answer was submitted too recently, and the next one has to wait
+    Wait(Duration),
+    /// The part is already solved, or the previous part isn't
+    WrongLevel,
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Verdict(Verdict::Correct) => write!(f, "That's the right answer"),
+            Response::Verdict(verdict) => {
+                write!(f, "That's not the right answer, it's {}", verdict)
+            }
+            Response::Wait(wait) => write!(
+                f,
+                "An answer was submitted too recently, wait {:?} before trying again",
+                wait
+            ),
+            Response::WrongLevel => write!(
+                f,
+                "The part is already solved, or the previous part is still unsolved"
+            ),
+        }
+    }
+}
+
+/// Parses the page returned after submitting an answer
+pub fn parse_response(page: &str) -> Result<Response, String> {
+    let message = article_text(page);
+
+    let response = if message.contains("That's the right answer") {
+        Response::Verdict(Verdict::Correct)
+    } else if message.contains("You gave an answer too recently") {
+        Response::Wait(parse_wait(&message).unwrap_or(Duration::from_secs(60)))
+    } else if message.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else if message.contains("your answer is too high") {
+        Response::Verdict(Verdict::TooHigh)
+    } else if message.contains("your answer is too low") {
+        Response::Verdict(Verdict::TooLow)
+    } else if message.contains("That's not the right answer") {
+        Response::Verdict(Verdict::Incorrect)
+    } else {
+        return Err(format!("Unexpected response: {}", message));
+    };
+
+    Ok(response)
+}
+
+/// The text of the article with the response, or of the entire page if there's none
+fn article_text(page: &str) -> String {
+    // Starts within the opening tag of the article, if there's one
+    let (article, mut in_tag) = page
+        .split_once("<article")
+        .and_then(|(_, article)| article.split_once("</article>"))
+        .map_or((page, false), |(article, _)| (article, true));
+
+    let mut text = String::new();
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Parses the wait in e.g. "You have 1m 5s left to wait."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (_, wait) = message.split_once("You have ")?;
+    let (wait, _) = wait.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|amount| {
+            let (value, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let value = value.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// A submitted answer along with its verdict
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Guess {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every answer submitted so far
+#[derive(Default, Deserialize, Serialize)]
+pub struct History {
+    #[serde(default, rename = "guess")]
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    /// Loads the history, which is empty if nothing was submitted yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+
+        match read_to_string(path) {
+            Ok(content) => {
+                toml::from_str(&content).map_err(|error| format!("{}: {}", path.display(), error))
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Could not read {}: {}", path.display(), error)),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let content = toml::to_string(self).map_err(|error| error.to_string())?;
+
+        write(path, content)
+            .map_err(|error| format!("Could not write {}: {}", path.display(), error))
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+
+    /// Every guess for a given year, day and part, in the order they were submitted
+    pub fn guesses(&self, year: usize, day: usize, part: usize) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| (guess.year, guess.day, guess.part) == (year, day, part))
+    }
+
+    /// The reason not to submit an answer, if the previous guesses show that it's wasted
+    pub fn refusal(&self, year: usize, day: usize, part: usize, answer: &str) -> Option<String> {
+        let guesses = self.guesses(year, day, part).collect::<Vec<&Guess>>();
+
+        if let Some(guess) = guesses
+            .iter()
+            .find(|guess| guess.verdict == Verdict::Correct)
+        {
+            return Some(format!("the part is already solved with {}", guess.answer));
+        }
+
+        if let Some(guess) = guesses.iter().find(|guess| guess.answer == answer) {
+            return Some(format!("{} was already {}", answer, guess.verdict));
+        }
+
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |verdict: Verdict| {
+            guesses
+                .iter()
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| guess.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|&high| value >= high) {
+            return Some(format!("{} was already too high", too_high));
+        }
+
+        if let Some(too_low) = bound(Verdict::TooLow).max().filter(|&low| value <= low) {
+            return Some(format!("{} was already too low", too_low));
+        }
+
+        None
+    }
+}
+
+/// Submits an answer, unless the history shows it's wasted, and records the verdict. Whitespace
+/// around the answer is left out, so it's compared with the history as it's submitted
+pub fn submit(
+    fetcher: &Fetcher,
+    history: &mut History,
+    year: usize,
+    day: usize,
+    part: usize,
+    answer: &str,
+) -> Result<Response, String> {
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        return Err(String::from("Not submitting an empty answer"));
+    }
+
+    if let Some(reason) = history.refusal(year, day, part, answer) {
+        return Err(format!("Not submitting {}, since {}", answer, reason));
+    }
+
+    let response = parse_response(&fetcher.answer(year, day, part, answer)?)?;
+
+    if let Response::Verdict(verdict) = response {
+        history.record(Guess {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::mock;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    fn guess(part: usize, answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            year: 2020,
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let responses = vec![
+            (
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+                Response::Verdict(Verdict::Correct),
+            ),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck, ...",
+                Response::Verdict(Verdict::TooHigh),
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Response::Verdict(Verdict::TooLow),
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+                Response::Verdict(Verdict::Incorrect),
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.",
+                Response::Wait(Duration::from_secs(65)),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Response::WrongLevel,
+            ),
+        ];
+
+        for (message, response) in responses {
+            assert_eq!(parse_response(&page(message)), Ok(response));
+        }
+
+        assert!(parse_response(&page("Something else")).is_err());
+    }
+
+    #[test]
+    fn test_refusal() {
+        let mut history = History::default();
+        history.record(guess(1, "200", Verdict::TooHigh));
+        history.record(guess(1, "300", Verdict::TooHigh));
+        history.record(guess(1, "100", Verdict::TooLow));
+        history.record(guess(1, "150", Verdict::Incorrect));
+        history.record(guess(2, "abc", Verdict::Correct));
+
+        assert_eq!(history.refusal(2020, 1, 1, "175"), None);
+        assert_eq!(
+            history.refusal(2020, 1, 1, "250"),
+            Some(String::from("200 was already too high"))
+        );
+        assert_eq!(
+            history.refusal(2020, 1, 1, "100"),
+            Some(String::from("100 was already too low"))
+        );
+        assert_eq!(
+            history.refusal(2020, 1, 1, "150"),
+            Some(String::from("150 was already incorrect"))
+        );
+        assert_eq!(
+            history.refusal(2020, 1, 2, "def"),
+            Some(String::from("the part is already solved with abc"))
+        );
+        assert_eq!(history.refusal(2020, 2, 1, "250"), None);
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("adventofcode-history-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut history = History::load(&path).unwrap();
+        history.record(guess(1, "200", Verdict::TooHigh));
+        history.save(&path).unwrap();
+
+        let history = History::load(&path).unwrap();
+        assert_eq!(
+            history.guesses(2020, 1, 1).collect::<Vec<&Guess>>(),
+            vec![&guess(1, "200", Verdict::TooHigh)]
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = mock::serve(vec![
+            (
+                200,
+                &page("That's not the right answer; your answer is too high."),
+            ),
+            (
+                200,
+                &page("You gave an answer too recently; You have 37s left to wait."),
+            ),
+            (200, &page("That's the right answer!")),
+        ]);
+
+        let fetcher = Fetcher::new(&base_url, "abc123", Duration::ZERO);
+        let mut history = History::default();
+
+        assert_eq!(
+            submit(&fetcher, &mut history, 2020, 1, 1, "1000"),
+            Ok(Response::Verdict(Verdict::TooHigh))
+        );
+        assert_eq!(
+            submit(&fetcher, &mut history, 2020, 1, 1, " 1000\n"),
+            Err(String::from(
+                "Not submitting 1000, since 1000 was already too high"
+            ))
+        );
+        assert_eq!(
+            submit(&fetcher, &mut history, 2020, 1, 1, "  "),
+            Err(String::from("Not submitting an empty answer"))
+        );
+        assert_eq!(
+            submit(&fetcher, &mut history, 2020, 1, 1, "1001"),
+            Err(String::from(
+                "Not submitting 1001, since 1000 was already too high"
+            ))
+        );
+        assert_eq!(
+            submit(&fetcher, &mut history, 2020, 1, 1, "514"),
+            Ok(Response::Wait(Duration::from_secs(37)))
+        );
+        assert_eq!(
+            submit(&fetcher, &mut history, 2020, 1, 1, "514"),
+            Ok(Response::Verdict(Verdict::Correct))
+        );
+
+        let requests = server.join().unwrap();
+
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, "/2020/day/1/answer");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc123"));
+        assert_eq!(requests[0].body, "level=1&answer=1000");
+        assert_eq!(
+            history.guesses(2020, 1, 1).collect::<Vec<&Guess>>(),
+            vec![
+                &guess(1, "1000", Verdict::TooHigh),
+                &guess(1, "514", Verdict::Correct)
+            ]
+        );
+    }
+}
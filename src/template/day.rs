@@ -1,13 +1,30 @@
-use crate::AdventOfCode;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day {}
 
-impl AdventOfCode for Day {
-    fn part_one(&self, input: &str) -> String {
-        input.to_string()
+impl Solution for Day {
+    type Parsed = String;
+
+    fn parse(&self, input: &str) -> Result<String, SolveError> {
+        Ok(input.to_string())
+    }
+
+    fn part_one(&self, input: &String, _ctx: &Context) -> Result<Answer, SolveError> {
+        Ok(input.as_str().into())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        input.to_string()
+    fn part_two(&self, input: &String, _ctx: &Context) -> Result<Answer, SolveError> {
+        Ok(input.as_str().into())
     }
 }
+
+register!(
+    42,
+    42,
+    Day {},
+    Puzzle {
+        title: "Untitled",
+        tags: &[],
+        description: None,
+    }
+);
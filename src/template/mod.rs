@@ -1,2 +1 @@
 mod day;
-pub use day::Day;
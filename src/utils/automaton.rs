@@ -0,0 +1,539 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::utils::grid::ADJACENT;
+use crate::utils::{Coordinate, Grid};
+use crate::{Context, SolveError};
+
+/// A way of advancing a state a generation at a time, where every cell changes at once
+pub trait Automaton<S> {
+    /// The generation following the state
+    fn step(&self, state: &S) -> S;
+
+    /// The state after the given number of generations
+    fn run(&self, mut state: S, generations: usize, ctx: &Context) -> Result<S, SolveError> {
+        for _ in 0..generations {
+            ctx.check()?;
+            state = self.step(&state);
+        }
+
+        Ok(state)
+    }
+
+    /// Writes the generation following the state into `next`, which can hold on to the memory of
+    /// an earlier generation, and returns whether it differs from the state
+    fn step_into(&self, state: &S, next: &mut S) -> bool
+    where
+        S: PartialEq,
+    {
+        *next = self.step(state);
+
+        *next != *state
+    }
+
+    /// Advances until a generation is the same as the one before it, returning the stable state
+    /// along with the number of generations it took to get there
+    fn run_until_stable(&self, mut state: S, ctx: &Context) -> Result<(S, usize), SolveError>
+    where
+        S: Clone + PartialEq,
+    {
+        // The two states take turns holding the next generation, so stepping doesn't allocate
+        let mut next = state.clone();
+        let mut generations = 0;
+
+        while self.step_into(&state, &mut next) {
+            ctx.check()?;
+            std::mem::swap(&mut state, &mut next);
+            generations += 1;
+        }
+
+        Ok((state, generations))
+    }
+
+    /// Every generation until the stable one, starting with the state itself, e.g. to show how the
+    /// automaton got there
+    fn generations_until_stable(&self, state: S, ctx: &Context) -> Result<Vec<S>, SolveError>
+    where
+        S: Clone + PartialEq,
+    {
+        let mut generations = vec![state];
+
+        loop {
+            let last = &generations[generations.len() - 1];
+            let mut next = last.clone();
+
+            if !self.step_into(last, &mut next) {
+                return Ok(generations);
+            }
+
+            ctx.check()?;
+            generations.push(next);
+        }
+    }
+
+    /// Advances until a state comes back, remembering every state seen so far by its hash
+    fn find_cycle(&self, mut state: S, ctx: &Context) -> Result<Cycle, SolveError>
+    where
+        S: Eq + Hash,
+    {
+        let mut seen = HashMap::new();
+
+        for generation in 0.. {
+            ctx.check()?;
+
+            let next = self.step(&state);
+
+            if let Some(start) = seen.insert(state, generation) {
+                return Ok(Cycle {
+                    start,
+                    length: generation - start,
+                });
+            }
+
+            state = next;
+        }
+
+        unreachable!("The generations never run out")
+    }
+}
+
+/// The generations of an automaton that repeat forever
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation of the cycle
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first generation with the same state as the given one, which can be reached without
+    /// running through the cycle over and over
+    pub fn equivalent(&self, generation: usize) -> usize {
+        if generation < self.start {
+            generation
+        } else {
+            self.start + (generation - self.start) % self.length
+        }
+    }
+}
+
+/// Which cells of a grid a cell looks at to decide its next value, at most one in each of the
+/// eight directions around it
+pub trait Neighbourhood<T> {
+    /// The neighbour of a cell in the direction, if it has one there
+    fn neighbour(
+        &self,
+        grid: &Grid<T>,
+        cell: Coordinate,
+        direction: Coordinate,
+    ) -> Option<Coordinate>;
+
+    /// The neighbours of a cell in every direction, clockwise from up like `ADJACENT`
+    fn neighbours(&self, grid: &Grid<T>, cell: Coordinate) -> [Option<Coordinate>; 8] {
+        ADJACENT.map(|direction| self.neighbour(grid, cell, direction))
+    }
+}
+
+/// The eight cells around a cell, orthogonally and diagonally
+pub struct Adjacent;
+
+impl<T> Neighbourhood<T> for Adjacent {
+    fn neighbour(
+        &self,
+        grid: &Grid<T>,
+        cell: Coordinate,
+        direction: Coordinate,
+    ) -> Option<Coordinate> {
+        Some(cell + direction).filter(|&neighbour| grid.contains(neighbour))
+    }
+}
+
+/// The first cell seen in each of the eight directions around a cell, looking past the cells
+/// that are transparent by the predicate
+pub struct LineOfSight<F>(pub F);
+
+impl<T, F> Neighbourhood<T> for LineOfSight<F>
+where
+    F: Fn(&T) -> bool,
+{
+    fn neighbour(
+        &self,
+        grid: &Grid<T>,
+        cell: Coordinate,
+        direction: Coordinate,
+    ) -> Option<Coordinate> {
+        grid.cast(cell, direction)
+            .find(|(_, value)| !(self.0)(value))
+            .map(|(neighbour, _)| neighbour)
+    }
+}
+
+/// The neighbours of every cell of a grid, as indices into the cells of the grid. They're looked
+/// up once, so stepping through the generations doesn't have to look for them again
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Table {
+    /// A slot per direction for every cell, clockwise from up like `ADJACENT`, which is empty
+    /// where the cell has no neighbour in that direction
+    neighbours: Vec<[Option<usize>; 8]>,
+}
+
+impl Table {
+    pub fn new<T, N>(grid: &Grid<T>, neighbourhood: &N) -> Self
+    where
+        N: Neighbourhood<T>,
+    {
+        Self {
+            neighbours: grid
+                .coordinates()
+                .map(|cell| {
+                    neighbourhood
+                        .neighbours(grid, cell)
+                        .map(|neighbour| neighbour.and_then(|neighbour| grid.index(neighbour)))
+                })
+                .collect(),
+        }
+    }
+
+    /// The neighbours of the cell at the index in every direction, clockwise from up like
+    /// `ADJACENT`
+    pub fn neighbours(&self, index: usize) -> &[Option<usize>; 8] {
+        &self.neighbours[index]
+    }
+
+    /// The neighbour of the cell at the index in the direction, which is one of `ADJACENT`
+    pub fn neighbour(&self, index: usize, direction: Coordinate) -> Option<usize> {
+        let slot = ADJACENT
+            .iter()
+            .position(|&adjacent| adjacent == direction)?;
+
+        self.neighbours[index][slot]
+    }
+
+    fn cells(&self) -> usize {
+        self.neighbours.len()
+    }
+}
+
+/// The values of the neighbours of a cell, which are only looked up as they're needed, so a rule
+/// can stop early or skip them altogether
+pub struct Neighbours<'a, T> {
+    cells: &'a [T],
+    slots: std::slice::Iter<'a, Option<usize>>,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let cells = self.cells;
+
+        self.slots.find_map(|&slot| slot.map(|index| &cells[index]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.slots.size_hint().1)
+    }
+}
+
+/// The rules of an automaton on a grid, where the next value of a cell follows from its value and
+/// the values of its neighbours
+pub struct Rules<F> {
+    table: Table,
+    transition: F,
+}
+
+impl<F> Rules<F> {
+    /// Looks up the neighbours of every cell of the grid once, so the rules only hold for grids of
+    /// the same size. A neighbourhood that depends on the values of the cells, like the line of
+    /// sight, is taken as it is in the given grid, e.g. where the floor of a waiting area never
+    /// changes
+    pub fn new<T, N>(grid: &Grid<T>, neighbourhood: N, transition: F) -> Self
+    where
+        N: Neighbourhood<T>,
+        F: Fn(&T, Neighbours<T>) -> T,
+    {
+        Self {
+            table: Table::new(grid, &neighbourhood),
+            transition,
+        }
+    }
+}
+
+impl<T, F> Automaton<Grid<T>> for Rules<F>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T, Neighbours<T>) -> T,
+{
+    fn step(&self, grid: &Grid<T>) -> Grid<T> {
+        let mut next = grid.clone();
+        self.step_into(grid, &mut next);
+
+        next
+    }
+
+    fn step_into(&self, grid: &Grid<T>, next: &mut Grid<T>) -> bool
+    where
+        Grid<T>: PartialEq,
+    {
+        assert_eq!(
+            grid.as_slice().len(),
+            self.table.cells(),
+            "The rules are for a grid of another size"
+        );
+
+        if (next.width(), next.height()) != (grid.width(), grid.height()) {
+            *next = grid.clone();
+        }
+
+        let cells = grid.as_slice();
+        let mut changed = false;
+
+        for (index, (value, next)) in cells.iter().zip(next.as_mut_slice()).enumerate() {
+            let neighbours = Neighbours {
+                cells,
+                slots: self.table.neighbours(index).iter(),
+            };
+
+            *next = (self.transition)(value, neighbours);
+            changed |= *next != *value;
+        }
+
+        changed
+    }
+}
+
+/// The rules of an automaton without bounds, e.g. in three or four dimensions, where only the
+/// active cells are kept. Whether a cell is active next follows from whether it's active now and
+/// the number of its active neighbours
+pub struct Sparse<N, F> {
+    neighbourhood: N,
+    transition: F,
+}
+
+impl<N, F> Sparse<N, F> {
+    pub fn new<P, I>(neighbourhood: N, transition: F) -> Self
+    where
+        N: Fn(P) -> I,
+        I: IntoIterator<Item = P>,
+        F: Fn(bool, usize) -> bool,
+    {
+        Self {
+            neighbourhood,
+            transition,
+        }
+    }
+}
+
+impl<P, I, N, F> Automaton<HashSet<P>> for Sparse<N, F>
+where
+    P: Copy + Eq + Hash,
+    N: Fn(P) -> I,
+    I: IntoIterator<Item = P>,
+    F: Fn(bool, usize) -> bool,
+{
+    fn step(&self, active: &HashSet<P>) -> HashSet<P> {
+        let mut counts = HashMap::new();
+
+        for &cell in active {
+            for neighbour in (self.neighbourhood)(cell) {
+                *counts.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+
+        // Inactive cells without active neighbours stay inactive, so they're never looked at
+        counts
+            .keys()
+            .chain(active.iter())
+            .filter(|&cell| {
+                (self.transition)(
+                    active.contains(cell),
+                    counts.get(cell).copied().unwrap_or(0),
+                )
+            })
+            .copied()
+            .collect()
+    }
+}
+
+/// Every point around a point in any number of dimensions, including the diagonal ones
+pub fn adjacent<const D: usize>(point: [isize; D]) -> impl Iterator<Item = [isize; D]> {
+    (0..3usize.pow(D as u32))
+        .map(move |mut offsets| {
+            let mut neighbour = point;
+
+            for axis in neighbour.iter_mut() {
+                *axis += (offsets % 3) as isize - 1;
+                offsets /= 3;
+            }
+
+            neighbour
+        })
+        .filter(move |&neighbour| neighbour != point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(input: &str) -> Grid<bool> {
+        Grid::parse(input, |c| Ok(c == '#')).unwrap()
+    }
+
+    fn show(grid: &Grid<bool>) -> String {
+        grid.map(|_, &alive| if alive { '#' } else { '.' })
+            .to_string()
+    }
+
+    fn conway(&alive: &bool, neighbours: Neighbours<bool>) -> bool {
+        let alive_neighbours = neighbours.filter(|&&neighbour| neighbour).count();
+
+        alive_neighbours == 3 || (alive && alive_neighbours == 2)
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let grid = life(".....\n.##..\n.#...\n.....");
+        let rules = Rules::new(&grid, Adjacent, conway);
+
+        let ctx = Context::default();
+
+        let (stable, generations) = rules.run_until_stable(grid.clone(), &ctx).unwrap();
+
+        assert_eq!(show(&stable), ".....\n.##..\n.##..\n.....");
+        assert_eq!(generations, 1);
+        assert_eq!(
+            rules.generations_until_stable(grid.clone(), &ctx).unwrap(),
+            vec![grid, stable]
+        );
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let blinker = life(".....\n.....\n.###.\n.....\n.....");
+        let rules = Rules::new(&blinker, Adjacent, conway);
+        let ctx = Context::default();
+
+        let cycle = rules.find_cycle(blinker.clone(), &ctx).unwrap();
+
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        assert_eq!(cycle.equivalent(1_000_001), 1);
+        assert_eq!(
+            show(&rules.run(blinker, 1, &ctx).unwrap()),
+            ".....\n..#..\n..#..\n..#..\n....."
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let grid = life("#..#\n....\n..#.");
+        let neighbours =
+            LineOfSight(|&alive: &bool| !alive).neighbours(&grid, Coordinate::new(0, 0));
+
+        assert_eq!(
+            neighbours,
+            [
+                None,
+                None,
+                Some(Coordinate::new(3, 0)),
+                Some(Coordinate::new(2, 2)),
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let grid = Grid::parse(
+            ".......#.
+...#.....
+.#.......
+.........
+..#L....#
+....#....
+.........
+#........
+...#.....",
+            Ok,
+        )
+        .unwrap();
+        let table = Table::new(&grid, &LineOfSight(|&c: &char| c == '.'));
+        let index = |x, y| grid.index(Coordinate::new(x, y)).unwrap();
+
+        assert_eq!(
+            table.neighbours(index(3, 4)),
+            &[
+                Some(index(3, 1)),
+                Some(index(7, 0)),
+                Some(index(8, 4)),
+                Some(index(4, 5)),
+                Some(index(3, 8)),
+                Some(index(0, 7)),
+                Some(index(2, 4)),
+                Some(index(1, 2)),
+            ]
+        );
+        assert_eq!(
+            table.neighbours(index(0, 0)),
+            &[
+                None,
+                None,
+                Some(index(7, 0)),
+                None,
+                Some(index(0, 7)),
+                None,
+                None,
+                None,
+            ]
+        );
+        assert_eq!(
+            table.neighbour(index(0, 0), Coordinate::new(0, 1)),
+            Some(index(0, 7))
+        );
+        assert_eq!(table.neighbour(index(0, 0), Coordinate::new(-1, 0)), None);
+
+        let adjacent = Table::new(&grid, &Adjacent);
+        assert_eq!(
+            adjacent.neighbour(index(0, 0), Coordinate::new(1, 1)),
+            Some(index(1, 1))
+        );
+        assert_eq!(
+            adjacent
+                .neighbours(index(0, 0))
+                .iter()
+                .filter(|neighbour| neighbour.is_some())
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_sparse() {
+        let ctx = Context::default();
+        let start = life(".#.\n..#\n###")
+            .iter()
+            .filter(|&(_, &alive)| alive)
+            .map(|(cell, _)| cell)
+            .collect::<Vec<Coordinate>>();
+        let rules = |alive: bool, neighbours: usize| neighbours == 3 || (alive && neighbours == 2);
+
+        let cubes = Sparse::new(adjacent::<3>, rules);
+        let cubes_start = start.iter().map(|cell| [cell.x, cell.y, 0]).collect();
+        assert_eq!(cubes.run(cubes_start, 6, &ctx).unwrap().len(), 112);
+
+        let hypercubes = Sparse::new(adjacent::<4>, rules);
+        let hypercubes_start = start.iter().map(|cell| [cell.x, cell.y, 0, 0]).collect();
+        assert_eq!(
+            hypercubes.run(hypercubes_start, 6, &ctx).unwrap().len(),
+            848
+        );
+    }
+}
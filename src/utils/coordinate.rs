@@ -0,0 +1,64 @@
+use std::ops::{Add, AddAssign, SubAssign};
+
+/// A position on a two-dimensional grid, or a movement across one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Coordinate {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Coordinate {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// The distance from the origin, moving along the axes
+    pub fn manhattan_distance(&self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+}
+
+impl Add<Coordinate> for Coordinate {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl AddAssign<Coordinate> for Coordinate {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl SubAssign<Coordinate> for Coordinate {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move() {
+        let mut coordinate = Coordinate::default();
+
+        coordinate += Coordinate::new(10, 4);
+        coordinate -= Coordinate::new(3, 12);
+
+        assert_eq!(coordinate, Coordinate::new(7, -8));
+        assert_eq!(coordinate.manhattan_distance(), 15);
+    }
+}
@@ -0,0 +1,353 @@
+use std::fmt;
+
+use crate::error::parse_lines;
+use crate::utils::Coordinate;
+use crate::SolveError;
+
+/// The directions to the four orthogonal neighbours of a cell: up, right, down and left
+pub const ORTHOGONAL: [Coordinate; 4] = [
+    Coordinate::new(0, -1),
+    Coordinate::new(1, 0),
+    Coordinate::new(0, 1),
+    Coordinate::new(-1, 0),
+];
+
+/// The directions to all eight neighbours of a cell, clockwise from up
+pub const ADJACENT: [Coordinate; 8] = [
+    Coordinate::new(0, -1),
+    Coordinate::new(1, -1),
+    Coordinate::new(1, 0),
+    Coordinate::new(1, 1),
+    Coordinate::new(0, 1),
+    Coordinate::new(-1, 1),
+    Coordinate::new(-1, 0),
+    Coordinate::new(-1, -1),
+];
+
+/// A rectangular grid of cells, stored row by row, where x grows to the right and y grows down
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid of the given size, with every cell set to the same value. A grid has at least one
+    /// cell, which is what lets it wrap around
+    pub fn new(width: usize, height: usize, cell: T) -> Result<Self, SolveError>
+    where
+        T: Clone,
+    {
+        if width == 0 || height == 0 {
+            return Err(SolveError::invalid(
+                "grid size",
+                &format!("{}x{}", width, height),
+            ));
+        }
+
+        Ok(Self {
+            cells: vec![cell; width * height],
+            width,
+            height,
+        })
+    }
+
+    /// Parses a map with a character per cell and a line per row, mapping each character to a
+    /// cell. Every row has to be as wide as the first
+    pub fn parse<F>(input: &str, cell: F) -> Result<Self, SolveError>
+    where
+        F: Fn(char) -> Result<T, SolveError>,
+    {
+        let width = input
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| SolveError::missing("grid"))?
+            .chars()
+            .count();
+
+        let rows = parse_lines(input, |line| {
+            if line.chars().count() != width {
+                return Err(SolveError::invalid("row width", line));
+            }
+
+            line.chars().map(&cell).collect::<Result<Vec<T>, _>>()
+        })?;
+
+        Ok(Self {
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            width,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coordinate: Coordinate) -> bool {
+        (0..self.width as isize).contains(&coordinate.x)
+            && (0..self.height as isize).contains(&coordinate.y)
+    }
+
+    /// The cell at the coordinate, if it's within the grid
+    pub fn get(&self, coordinate: Coordinate) -> Option<&T> {
+        self.index(coordinate).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, coordinate: Coordinate) -> Option<&mut T> {
+        self.index(coordinate)
+            .map(move |index| &mut self.cells[index])
+    }
+
+    /// The cell at the coordinate, treating the grid as a torus that repeats in every direction
+    pub fn get_wrapping(&self, coordinate: Coordinate) -> &T {
+        let x = coordinate.x.rem_euclid(self.width as isize) as usize;
+        let y = coordinate.y.rem_euclid(self.height as isize) as usize;
+
+        &self.cells[y * self.width + x]
+    }
+
+    /// Every coordinate of the grid, row by row
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        (0..height).flat_map(move |y| (0..width).map(move |x| Coordinate::new(x, y)))
+    }
+
+    /// Every cell of the grid along with its coordinate, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.coordinates().zip(self.cells.iter())
+    }
+
+    /// Every cell of the grid, row by row, where a cell is found at the index of its coordinate
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// The orthogonal neighbours of a cell that are within the grid
+    pub fn neighbours4(&self, coordinate: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.neighbours(coordinate, &ORTHOGONAL)
+    }
+
+    /// The orthogonal and diagonal neighbours of a cell that are within the grid
+    pub fn neighbours8(&self, coordinate: Coordinate) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.neighbours(coordinate, &ADJACENT)
+    }
+
+    /// The cells from a cell in a direction, excluding the cell itself, until the edge of the grid
+    pub fn cast(
+        &self,
+        from: Coordinate,
+        direction: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> {
+        std::iter::successors(Some(from + direction), move |&coordinate| {
+            Some(coordinate + direction)
+        })
+        .map_while(move |coordinate| Some((coordinate, self.get(coordinate)?)))
+    }
+
+    /// A grid of the same size, with each cell mapped from the cell at the same coordinate
+    pub fn map<U, F>(&self, mut cell: F) -> Grid<U>
+    where
+        F: FnMut(Coordinate, &T) -> U,
+    {
+        Grid {
+            cells: self
+                .iter()
+                .map(|(coordinate, value)| cell(coordinate, value))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        coordinate: Coordinate,
+        directions: &'static [Coordinate],
+    ) -> impl Iterator<Item = (Coordinate, &'a T)> {
+        directions.iter().filter_map(move |&direction| {
+            let neighbour = coordinate + direction;
+
+            Some((neighbour, self.get(neighbour)?))
+        })
+    }
+
+    /// Where the cell at the coordinate is found in the cells of the grid, row by row
+    pub fn index(&self, coordinate: Coordinate) -> Option<usize> {
+        if self.contains(coordinate) {
+            Some(coordinate.y as usize * self.width + coordinate.x as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Renders the grid with a line per row, and without a trailing newline
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |c| {
+            c.to_digit(10)
+                .ok_or_else(|| SolveError::invalid("digit", &c.to_string()))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456\n");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Coordinate::new(2, 1)), Some(&6));
+        assert_eq!(grid.get(Coordinate::new(3, 1)), None);
+        assert_eq!(grid.get(Coordinate::new(0, -1)), None);
+        assert_eq!(grid.index(Coordinate::new(2, 1)), Some(5));
+        assert_eq!(grid.as_slice()[5], 6);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let error = Grid::parse("12\n3", Ok).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Invalid("row width"));
+        assert_eq!(error.line, Some(2));
+
+        assert_eq!(
+            Grid::parse("", Ok).unwrap_err().kind,
+            ErrorKind::Missing("grid")
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        for (width, height) in [(0, 0), (0, 2), (2, 0)] {
+            assert_eq!(
+                Grid::new(width, height, 7).unwrap_err().kind,
+                ErrorKind::Invalid("grid size")
+            );
+        }
+
+        assert_eq!(
+            Grid::parse("\n12", Ok).unwrap_err().kind,
+            ErrorKind::Missing("grid")
+        );
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = digits("123\n456");
+
+        assert_eq!(grid.get_wrapping(Coordinate::new(4, 0)), &2);
+        assert_eq!(grid.get_wrapping(Coordinate::new(-1, -1)), &6);
+        assert_eq!(grid.get_wrapping(Coordinate::new(7, 3)), &5);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789");
+        let values = |neighbours: Vec<(Coordinate, &u32)>| {
+            neighbours
+                .into_iter()
+                .map(|(_, &value)| value)
+                .collect::<Vec<u32>>()
+        };
+
+        assert_eq!(
+            values(grid.neighbours4(Coordinate::new(1, 1)).collect()),
+            vec![2, 6, 8, 4]
+        );
+        assert_eq!(
+            values(grid.neighbours8(Coordinate::new(1, 1)).collect()),
+            vec![2, 3, 6, 9, 8, 7, 4, 1]
+        );
+        assert_eq!(
+            values(grid.neighbours8(Coordinate::new(0, 0)).collect()),
+            vec![2, 5, 4]
+        );
+    }
+
+    #[test]
+    fn test_cast() {
+        let grid = digits("1234\n5678\n9012");
+
+        assert_eq!(
+            grid.cast(Coordinate::new(0, 0), Coordinate::new(1, 1))
+                .map(|(_, &value)| value)
+                .collect::<Vec<u32>>(),
+            vec![6, 1]
+        );
+        assert_eq!(
+            grid.cast(Coordinate::new(0, 0), Coordinate::new(-1, 0))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits("123\n456");
+
+        assert_eq!(
+            grid.rows().collect::<Vec<&[u32]>>(),
+            vec![[1, 2, 3], [4, 5, 6]]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect())
+                .collect::<Vec<Vec<u32>>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = digits("12\n34");
+        assert_eq!(Grid::new(2, 1, 7).unwrap().to_string(), "77");
+
+        let mut doubled = grid.map(|_, &value| value * 2);
+
+        *doubled.get_mut(Coordinate::new(0, 0)).unwrap() = 0;
+
+        assert_eq!(doubled.to_string(), "04\n68");
+        assert_eq!(
+            grid.map(|coordinate, _| coordinate.x + coordinate.y)
+                .to_string(),
+            "01\n12"
+        );
+    }
+}
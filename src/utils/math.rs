@@ -0,0 +1,58 @@
+/// The extended Euclidean algorithm, returning the greatest common divisor of `a` and `b` along
+/// with the coefficients `x` and `y` such that `a * x + b * y` is the divisor
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (g, x, y) = egcd(b % a, a);
+        (g, y - (b / a) * x, x)
+    }
+}
+
+/// The modular multiplicative inverse of `a` modulo `modulus`, if they're coprime
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = egcd(a, modulus);
+    if g == 1 {
+        Some((x % modulus + modulus) % modulus)
+    } else {
+        None
+    }
+}
+
+/// The Chinese remainder theorem: the smallest non-negative number that leaves each residue when
+/// divided by its modulus, if the moduli are pairwise coprime
+pub fn crt(residues: &[i64], moduli: &[i64]) -> Option<i64> {
+    let prod = moduli.iter().product::<i64>();
+
+    let mut sum = 0;
+
+    for (&residue, &modulus) in residues.iter().zip(moduli) {
+        let p = prod / modulus;
+        sum += residue * mod_inv(p, modulus)? * p
+    }
+
+    Some(sum % prod)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_egcd() {
+        assert_eq!(egcd(240, 46), (2, -9, 47));
+        assert_eq!(egcd(0, 7), (7, 0, 1));
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(4, 8), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[2, 3, 2], &[3, 5, 7]), Some(23));
+        assert_eq!(crt(&[1, 1], &[4, 6]), None);
+    }
+}
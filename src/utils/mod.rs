@@ -0,0 +1,10 @@
+//! Helpers that keep coming back between puzzles, shared by the solvers and usable on their own
+
+pub mod automaton;
+pub mod coordinate;
+pub mod grid;
+pub mod math;
+
+pub use automaton::Automaton;
+pub use coordinate::Coordinate;
+pub use grid::Grid;
@@ -0,0 +1,294 @@
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::utils::Grid;
+use crate::{runner, Context, Params, Solver};
+
+/// How long each frame is shown for when no delay is given
+pub const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// A colour of a tile, as red, green and blue
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+}
+
+/// A tile of a frame, drawn as a character in a colour
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Colour,
+}
+
+/// How the tiles of a grid a solver simulates are drawn, e.g. seats in a waiting area
+pub trait Draw {
+    fn glyph(&self) -> char;
+    fn colour(&self) -> Colour;
+}
+
+/// A snapshot of the grid a part simulates, e.g. a single generation of an automaton
+pub type Frame = Grid<Cell>;
+
+/// Draws every tile of a grid
+pub fn frame<T: Draw>(grid: &Grid<T>) -> Frame {
+    grid.map(|_, tile| Cell {
+        glyph: tile.glyph(),
+        colour: tile.colour(),
+    })
+}
+
+/// Parses the input once, and records the frames of a part with the given parameters
+pub fn frames(
+    day: &dyn Solver,
+    part: usize,
+    input: &str,
+    params: &Params,
+) -> Result<Vec<Frame>, String> {
+    params.check(day.params())?;
+    let ctx = Context::default().with_params(params.clone());
+
+    let parsed = runner::parse(day, input)?;
+
+    day.frames(parsed.as_ref(), part, &ctx)
+        .map_err(|error| error.to_string())
+}
+
+/// Renders a frame with ANSI colours, switching colour only where it changes within a row
+fn render(frame: &Frame) -> String {
+    let mut text = String::new();
+
+    for row in frame.rows() {
+        let mut colour = None;
+
+        for cell in row {
+            if colour != Some(cell.colour) {
+                colour = Some(cell.colour);
+
+                let Colour { red, green, blue } = cell.colour;
+                let _ = write!(text, "\x1b[38;2;{};{};{}m", red, green, blue);
+            }
+
+            text.push(cell.glyph);
+        }
+
+        text.push_str("\x1b[0m\n");
+    }
+
+    text
+}
+
+/// A key pressed while the frames are playing
+enum Key {
+    Pause,
+    Next,
+    Previous,
+    Quit,
+}
+
+impl Key {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b' ' => Some(Key::Pause),
+            b'n' | b'l' => Some(Key::Next),
+            b'p' | b'h' => Some(Key::Previous),
+            // Ctrl-C arrives as a byte, as the terminal doesn't turn it into a signal while playing
+            b'q' | 0x03 => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// Plays frames in the terminal, one after another with a delay between them. While playing,
+/// space pauses and resumes, n and p step to the next and previous frame, and q quits. The keys
+/// are only read if stdin is a terminal, which is restored once the player is dropped
+pub struct Player {
+    delay: Duration,
+    keys: Option<Receiver<Key>>,
+    _mode: Option<Mode>,
+}
+
+impl Player {
+    pub fn new(delay: Duration) -> Self {
+        if !io::stdin().is_terminal() {
+            return Self {
+                delay,
+                keys: None,
+                _mode: None,
+            };
+        }
+
+        let mode = Mode::unbuffered();
+        let (sender, receiver) = mpsc::channel();
+
+        // The thread is left waiting on the next key once the player is done, as reading stdin
+        // can't be interrupted
+        thread::spawn(move || {
+            for byte in io::stdin().lock().bytes() {
+                let key = match byte {
+                    Ok(byte) => Key::from_byte(byte),
+                    Err(_) => break,
+                };
+
+                if let Some(key) = key {
+                    if sender.send(key).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            delay,
+            keys: Some(receiver),
+            _mode: Some(mode),
+        }
+    }
+
+    /// Plays the frames, labelled with the given label, until the last one is shown. Returns
+    /// false if q was pressed
+    pub fn play(&mut self, label: &str, frames: &[Frame]) -> bool {
+        let mut stdout = io::stdout().lock();
+        let (mut index, mut paused) = (0, false);
+
+        // Writing to stdout is best effort, as a closed stdout has no one left to show frames to
+        let _ = write!(stdout, "\x1b[?25l");
+
+        let quit = loop {
+            let frame = match frames.get(index) {
+                Some(frame) => frame,
+                None => break false,
+            };
+
+            let _ = write!(
+                stdout,
+                "\x1b[H\x1b[2J{}{} frame {}/{}{}",
+                render(frame),
+                label,
+                index + 1,
+                frames.len(),
+                if paused { " (paused)" } else { "" }
+            );
+            let _ = stdout.flush();
+
+            let key = match &self.keys {
+                Some(keys) if paused => keys.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                Some(keys) => keys.recv_timeout(self.delay),
+                None => {
+                    thread::sleep(self.delay);
+
+                    Err(RecvTimeoutError::Timeout)
+                }
+            };
+
+            match key {
+                Ok(Key::Pause) => paused = !paused,
+                Ok(Key::Next) => {
+                    paused = true;
+                    index = (index + 1).min(frames.len() - 1);
+                }
+                Ok(Key::Previous) => {
+                    paused = true;
+                    index = index.saturating_sub(1);
+                }
+                Ok(Key::Quit) => break true,
+                Err(RecvTimeoutError::Timeout) => index += 1,
+                // Without keys there's no way to resume, so the frames play on by themselves
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.keys = None;
+                    paused = false;
+                }
+            }
+        };
+
+        let _ = writeln!(stdout, "\x1b[?25h");
+
+        !quit
+    }
+}
+
+/// The settings of the terminal before it was switched to reading single keys, which are
+/// restored when it's dropped
+struct Mode(Option<String>);
+
+impl Mode {
+    /// Stops the terminal from buffering lines, echoing keys and turning Ctrl-C into a signal.
+    /// Relies on stty, without which the keys are only read once a line is entered
+    fn unbuffered() -> Self {
+        let saved = Command::new("stty")
+            .arg("-g")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+        if saved.is_some() {
+            let _ = Command::new("stty")
+                .args(["-icanon", "-echo", "-isig", "min", "1"])
+                .status();
+        }
+
+        Self(saved)
+    }
+}
+
+impl Drop for Mode {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.0 {
+            let _ = Command::new("stty").arg(saved).status();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SolveError;
+
+    enum Tile {
+        Wall,
+        Open,
+    }
+
+    impl Draw for Tile {
+        fn glyph(&self) -> char {
+            match self {
+                Tile::Wall => '#',
+                Tile::Open => '.',
+            }
+        }
+
+        fn colour(&self) -> Colour {
+            match self {
+                Tile::Wall => Colour::rgb(255, 0, 0),
+                Tile::Open => Colour::rgb(0, 0, 255),
+            }
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::parse("##.\n...", |c| match c {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Open),
+            _ => Err(SolveError::invalid("tile", &c.to_string())),
+        })
+        .unwrap();
+
+        assert_eq!(
+            render(&frame(&grid)),
+            "\x1b[38;2;255;0;0m##\x1b[38;2;0;0;255m.\x1b[0m\n\x1b[38;2;0;0;255m...\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::answers::NamedAnswers;
+use crate::inputs::{self, Inputs};
+use crate::registry::Registration;
+use crate::{runner, scaffold};
+
+/// How often the watched files are checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification times of the watched files, including the files in watched directories
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The answers of a single run, keyed by input and part
+type Answers = BTreeMap<(String, usize), Result<String, String>>;
+
+/// The environment variable the answers are passed on in when the CLI restarts after a rebuild,
+/// so the first run of the rebuilt CLI is compared with the last run before it
+const PREVIOUS_ANSWERS: &str = "AOC_WATCH_PREVIOUS_ANSWERS";
+
+/// Runs a day whenever its input, its named inputs or its module changes, and prints how the
+/// answers differ from the previous run. Input changes are run in-process, while module changes
+/// rebuild the CLI and restart it, or print the compile errors if it doesn't build. Only returns
+/// if the CLI can't be restarted, with the reason
+pub fn watch(
+    registration: &Registration,
+    inputs: &Inputs,
+    input_dir: &Path,
+    parts: &[usize],
+    options: &runner::Options,
+) -> String {
+    // The executable is replaced by a rebuild, so it's located before that happens
+    let executable = env::current_exe();
+    let source = scaffold::day_path(Path::new(""), registration.year, registration.day);
+
+    let paths = vec![
+        inputs.path(registration.year, registration.day),
+        inputs::named_dir(input_dir, registration.year, registration.day),
+        source.clone(),
+    ];
+
+    paths
+        .iter()
+        .for_each(|path| eprintln!("watching {}", path.display()));
+
+    let previous = env::var(PREVIOUS_ANSWERS)
+        .ok()
+        .and_then(|previous| decode(&previous))
+        .unwrap_or_default();
+
+    let mut answers = run(registration, inputs, input_dir, parts, options);
+    print_diff(&previous, &answers);
+
+    let mut snapshot = snapshot(&paths);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = self::snapshot(&paths);
+        let changed = changed(&snapshot, &current);
+        snapshot = current;
+
+        if changed.is_empty() {
+            continue;
+        }
+
+        changed
+            .iter()
+            .for_each(|path| eprintln!("changed {}", path.display()));
+
+        if changed.contains(&source) {
+            match rebuild() {
+                Ok(()) => return restart(&executable, &answers),
+                Err(errors) => {
+                    eprintln!("{}", errors);
+
+                    continue;
+                }
+            }
+        }
+
+        let previous = answers;
+        answers = run(registration, inputs, input_dir, parts, options);
+        print_diff(&previous, &answers);
+    }
+}
+
+/// Runs the day against the input, and every named input unless the input is a named one
+fn run(
+    registration: &Registration,
+    inputs: &Inputs,
+    input_dir: &Path,
+    parts: &[usize],
+    options: &runner::Options,
+) -> Answers {
+    let (year, day) = (registration.year, registration.day);
+
+    // The answers are only needed for the parameters, so the named inputs run without them if
+    // they can't be read, as they would without the file
+    let answers = NamedAnswers::load(input_dir, year, day).unwrap_or_default();
+
+    let names = match inputs.name() {
+        Some(_) => Vec::new(),
+        None => inputs::names(input_dir, year, day),
+    };
+
+    let named = names.into_iter().map(|name| {
+        let named = Inputs::new(input_dir).named(Some(name.clone()));
+        let options = answers.options(&name, options);
+
+        (name, runner::run(registration, &named, parts, &options))
+    });
+
+    let (label, options) = match inputs.name() {
+        Some(name) => (name.to_string(), answers.options(name, options)),
+        None => (String::from("input"), options.clone()),
+    };
+
+    std::iter::once((label, runner::run(registration, inputs, parts, &options)))
+        .chain(named)
+        .flat_map(|(name, outcomes)| {
+            outcomes.into_iter().map(move |outcome| {
+                let answer = outcome.answer.map(|answer| answer.to_string());
+
+                ((name.clone(), outcome.part), answer)
+            })
+        })
+        .collect()
+}
+
+/// A row for every answer, along with the previous answer if it changed
+fn diff(previous: &Answers, current: &Answers) -> Vec<Vec<String>> {
+    let show = |answer: &Result<String, String>| match answer {
+        Ok(answer) => answer.clone(),
+        Err(error) => format!("error: {}", error),
+    };
+
+    current
+        .iter()
+        .map(|(key, answer)| {
+            let was = match previous.get(key) {
+                Some(previous) if previous != answer => show(previous),
+                _ => String::new(),
+            };
+
+            vec![key.0.clone(), key.1.to_string(), show(answer), was]
+        })
+        .collect()
+}
+
+fn print_diff(previous: &Answers, current: &Answers) {
+    runner::print_rows(
+        &["input", "part", "answer", "previous"],
+        &diff(previous, current),
+    );
+}
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .flat_map(|path| match fs::read_dir(path) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .collect::<Vec<PathBuf>>(),
+            Err(_) => vec![path.clone()],
+        })
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|metadata| metadata.modified());
+
+            Some((path, modified.ok()?))
+        })
+        .collect()
+}
+
+/// Every path that was created, changed or removed between two snapshots
+fn changed(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .chain(
+            previous
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        )
+        .collect::<Vec<PathBuf>>();
+
+    changed.sort_unstable();
+
+    changed
+}
+
+/// Builds the CLI with the same profile, returning the compile errors if it fails
+fn rebuild() -> Result<(), String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+
+    let mut command = Command::new(cargo);
+    command.args(["build", "--color", "always"]);
+
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    let output = command
+        .output()
+        .map_err(|error| format!("Could not run cargo: {}", error))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).into_owned())
+    }
+}
+
+/// The answers as JSON, as a list since JSON only has string keys
+fn encode(answers: &Answers) -> String {
+    serde_json::to_string(&answers.iter().collect::<Vec<_>>()).expect("Answers are serializable")
+}
+
+/// The answers passed on by `encode`, unless they can't be read
+fn decode(answers: &str) -> Option<Answers> {
+    serde_json::from_str::<Vec<((String, usize), Result<String, String>)>>(answers)
+        .ok()
+        .map(|answers| answers.into_iter().collect())
+}
+
+/// Replaces the running CLI with the rebuilt one, with the same arguments, passing on the answers
+/// of the last run. Only returns if it can't, with the reason
+fn restart(executable: &std::io::Result<PathBuf>, answers: &Answers) -> String {
+    let executable = match executable {
+        Ok(executable) => executable,
+        Err(error) => return format!("Could not locate the executable: {}", error),
+    };
+
+    let mut command = Command::new(executable);
+    command
+        .args(env::args_os().skip(1))
+        .env(PREVIOUS_ANSWERS, encode(answers));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        let error = command.exec();
+        format!("Could not restart {}: {}", executable.display(), error)
+    }
+
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => format!("Could not restart {}: {}", executable.display(), error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let time = SystemTime::UNIX_EPOCH;
+        let later = time + Duration::from_secs(1);
+
+        let previous = vec![
+            (PathBuf::from("data/2022/02.txt"), time),
+            (PathBuf::from("data/2022/02/example1.txt"), time),
+            (PathBuf::from("src/years/year2022/day02.rs"), time),
+        ]
+        .into_iter()
+        .collect::<Snapshot>();
+        let current = vec![
+            (PathBuf::from("data/2022/02.txt"), later),
+            (PathBuf::from("data/2022/02/example2.txt"), time),
+            (PathBuf::from("src/years/year2022/day02.rs"), time),
+        ]
+        .into_iter()
+        .collect::<Snapshot>();
+
+        assert_eq!(
+            changed(&previous, &current),
+            vec![
+                PathBuf::from("data/2022/02/example1.txt"),
+                PathBuf::from("data/2022/02/example2.txt"),
+                PathBuf::from("data/2022/02.txt"),
+            ]
+        );
+        assert!(changed(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_previous_answers() {
+        let answers = vec![
+            ((String::from("example1"), 1), Ok(String::from("15"))),
+            ((String::from("input"), 2), Err(String::from("no solution"))),
+        ]
+        .into_iter()
+        .collect::<Answers>();
+
+        assert_eq!(decode(&encode(&answers)), Some(answers));
+        assert_eq!(decode("not json"), None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let answer = |name: &str, part: usize, answer: Result<&str, &str>| {
+            (
+                (name.to_string(), part),
+                answer.map(String::from).map_err(String::from),
+            )
+        };
+
+        let previous = vec![
+            answer("example1", 1, Ok("15")),
+            answer("input", 1, Ok("14")),
+            answer("input", 2, Err("no solution")),
+        ]
+        .into_iter()
+        .collect::<Answers>();
+        let current = vec![
+            answer("example1", 1, Ok("15")),
+            answer("example2", 1, Ok("12")),
+            answer("input", 1, Ok("15")),
+            answer("input", 2, Ok("12")),
+        ]
+        .into_iter()
+        .collect::<Answers>();
+
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                vec!["example1", "1", "15", ""],
+                vec!["example2", "1", "12", ""],
+                vec!["input", "1", "15", "14"],
+                vec!["input", "2", "12", "error: no solution"],
+            ]
+        );
+    }
+}
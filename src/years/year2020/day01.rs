@@ -1,13 +1,27 @@
-use crate::AdventOfCode;
+use crate::error::{parse_lines, parse_number};
+use crate::{register, Answer, Context, Param, Puzzle, Solution, SolveError};
+
+const TARGET: Param = Param {
+    name: "target",
+    default: "2020",
+    description: "The sum the entries have to add up to",
+};
 
 pub struct Day01 {}
 
-impl AdventOfCode for Day01 {
-    fn part_one(&self, input: &str) -> String {
-        let lines = input
-            .lines()
-            .map(|line| line.parse::<usize>().expect("Not a number"))
-            .collect::<Vec<usize>>();
+impl Solution for Day01 {
+    type Parsed = Vec<usize>;
+
+    fn params(&self) -> &'static [Param] {
+        &[TARGET]
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, SolveError> {
+        parse_lines(input, parse_number::<usize>)
+    }
+
+    fn part_one(&self, lines: &Vec<usize>, ctx: &Context) -> Result<Answer, SolveError> {
+        let target = ctx.param::<usize>(&TARGET)?;
 
         lines
             .iter()
@@ -15,23 +29,17 @@ impl AdventOfCode for Day01 {
             .find_map(|(first_index, first)| {
                 let next_index_from_first_traversed = first_index + 1;
 
-                match lines[next_index_from_first_traversed..]
+                lines[next_index_from_first_traversed..]
                     .iter()
-                    .find(|&second| first + second == 2020)
-                {
-                    Some(matching) => Some(first * matching),
-                    None => None,
-                }
+                    .find(|&second| first + second == target)
+                    .map(|matching| first * matching)
             })
-            .expect("No matches")
-            .to_string()
+            .ok_or_else(|| SolveError::no_solution("no matches"))
+            .map(Answer::from)
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let lines = input
-            .lines()
-            .map(|line| line.parse::<usize>().expect("Not a number"))
-            .collect::<Vec<usize>>();
+    fn part_two(&self, lines: &Vec<usize>, ctx: &Context) -> Result<Answer, SolveError> {
+        let target = ctx.param::<usize>(&TARGET)?;
 
         lines
             .iter()
@@ -46,61 +54,63 @@ impl AdventOfCode for Day01 {
                         let next_index_from_second_traversed =
                             next_index_from_first_traversed + second_index + 1;
 
-                        match lines[next_index_from_second_traversed..]
+                        lines[next_index_from_second_traversed..]
                             .iter()
-                            .find(|&third| first + second + third == 2020)
-                        {
-                            Some(matching) => Some(first * second * matching),
-                            None => None,
-                        }
+                            .find(|&third| first + second + third == target)
+                            .map(|matching| first * second * matching)
                     })
             })
-            .expect("No matches")
-            .to_string()
+            .ok_or_else(|| SolveError::no_solution("no matches"))
+            .map(Answer::from)
     }
 }
 
+register!(
+    2020,
+    1,
+    Day01 {},
+    Puzzle {
+        title: "Report Repair",
+        tags: &["brute force"],
+        description: Some("Tries every pair and triple of entries until they add up to the target"),
+    }
+);
+
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
-
     use super::*;
+    use crate::ErrorKind;
 
     #[test]
-    fn test_examples_part_one() {
-        let input = String::from("1721\n979\n366\n299\n675\n1456");
-        assert_eq!((Day01 {}).part_one(&input), "514579");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/01.txt").expect("Could not read input file");
-        assert_eq!((Day01 {}).part_one(&input), "876459");
-    }
-
-    #[test]
-    #[should_panic(expected = "No matches")]
     fn test_should_not_match_itself_part_one() {
         let input = String::from("220\n1010");
-        (Day01 {}).part_one(&input);
+        assert_eq!(
+            (Day01 {})
+                .part_one(&(Day01 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap_err()
+                .kind,
+            ErrorKind::NoSolution("no matches")
+        );
     }
 
     #[test]
-    fn test_examples_part_two() {
-        let input = String::from("1721\n979\n366\n299\n675\n1456");
-        assert_eq!((Day01 {}).part_two(&input), "241861950");
+    fn test_should_not_match_itself_part_two() {
+        let input = String::from("100\n20\n1000");
+        assert_eq!(
+            (Day01 {})
+                .part_two(&(Day01 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap_err()
+                .kind,
+            ErrorKind::NoSolution("no matches")
+        );
     }
 
     #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/01.txt").expect("Could not read input file");
-        assert_eq!((Day01 {}).part_two(&input), "116168640");
-    }
+    fn test_invalid_input() {
+        let input = String::from("1721\n979\nabc");
+        let error = (Day01 {}).parse(&input).unwrap_err();
 
-    #[test]
-    #[should_panic(expected = "No matches")]
-    fn test_should_not_match_itself_part_two() {
-        let input = String::from("100\n20\n1000");
-        println!("{}", (Day01 {}).part_two(&input));
+        assert_eq!(error.kind, ErrorKind::NotANumber);
+        assert_eq!(error.line, Some(3));
     }
 }
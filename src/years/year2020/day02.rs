@@ -1,125 +1,106 @@
-use crate::AdventOfCode;
+use crate::error::{parse_lines, parse_number};
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day02 {}
 
-struct Rule<'a> {
-    policy: &'a str,
-    password: &'a str,
+pub struct Rule {
+    policy: Policy,
+    password: String,
 }
 
-impl<'a> Rule<'a> {
-    fn new_from_str(rule: &'a str) -> Self {
-        let mut split = rule.split(':');
-        let (policy, password) = (
-            split.next().expect("No policy provided"),
-            split.next().expect("No password provided").trim(),
-        );
+impl Rule {
+    fn new_from_str(rule: &str) -> Result<Self, SolveError> {
+        let (policy, password) = rule
+            .split_once(':')
+            .ok_or_else(|| SolveError::invalid("rule", rule))?;
 
-        Self { policy, password }
+        Ok(Self {
+            policy: Policy::new_from_str(policy)?,
+            password: password.trim().to_string(),
+        })
     }
 }
 
-struct Policy<'a> {
-    letter: &'a str,
+struct Policy {
+    letter: String,
     min: usize,
     max: usize,
 }
 
-impl<'a> Policy<'a> {
-    fn new_from_str(policy: &'a str) -> Self {
+impl Policy {
+    fn new_from_str(policy: &str) -> Result<Self, SolveError> {
         let mut policy_details = policy.split_whitespace();
         let (range, letter) = (
-            policy_details.next().expect("No range provided"),
-            policy_details.next().expect("No letter provided"),
-        );
-
-        let mut count = range.split('-');
-        let (min, max) = (
-            count
+            policy_details
                 .next()
-                .expect("No min value provided")
-                .parse::<usize>()
-                .expect("Min value not a number"),
-            count
+                .ok_or_else(|| SolveError::missing("range"))?,
+            policy_details
                 .next()
-                .expect("No max value provided")
-                .parse::<usize>()
-                .expect("Max value not a number"),
+                .ok_or_else(|| SolveError::missing("letter"))?,
         );
 
-        Self { letter, min, max }
-    }
-}
+        let (min, max) = range
+            .split_once('-')
+            .ok_or_else(|| SolveError::invalid("range", range))?;
 
-impl AdventOfCode for Day02 {
-    fn part_one(&self, input: &str) -> String {
-        let lines = input.lines();
-        lines
-            .filter(|&line| {
-                let Rule { policy, password } = Rule::new_from_str(line);
-                let Policy { letter, min, max } = Policy::new_from_str(policy);
-
-                let occurences = password.matches(letter).count();
-
-                occurences >= min && occurences <= max
-            })
-            .count()
-            .to_string()
+        Ok(Self {
+            letter: letter.to_string(),
+            min: parse_number(min)?,
+            max: parse_number(max)?,
+        })
     }
+}
 
-    fn part_two(&self, input: &str) -> String {
-        let lines = input.lines();
-        lines
-            .filter(|&line| {
-                let Rule { policy, password } = Rule::new_from_str(line);
-                let Policy { letter, min, max } = Policy::new_from_str(policy);
-
-                let password_char_at_first_pos = password.chars().nth(min - 1).unwrap();
-                let password_char_at_last_pos = password.chars().nth(max - 1).unwrap();
+impl Solution for Day02 {
+    type Parsed = Vec<Rule>;
 
-                (password_char_at_first_pos.to_string() == letter)
-                    ^ (password_char_at_last_pos.to_string() == letter)
-            })
-            .count()
-            .to_string()
+    fn parse(&self, input: &str) -> Result<Vec<Rule>, SolveError> {
+        parse_lines(input, Rule::new_from_str)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
+    fn part_one(&self, rules: &Vec<Rule>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let valid = rules.iter().filter(|Rule { policy, password }| {
+            let occurences = password.matches(&policy.letter).count();
 
-    use super::*;
+            occurences >= policy.min && occurences <= policy.max
+        });
 
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "1-3 a: abcde
-1-3 b: cdefg
-2-9 c: ccccccccc",
-        );
-        assert_eq!((Day02 {}).part_one(&input), "2");
+        Ok(valid.count().into())
     }
 
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/02.txt").expect("Could not read input file");
-        assert_eq!((Day02 {}).part_one(&input), "600");
-    }
+    fn part_two(&self, rules: &Vec<Rule>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let valid = rules
+            .iter()
+            .enumerate()
+            .map(|(index, Rule { policy, password })| {
+                let password_char_at = |position: usize| {
+                    position
+                        .checked_sub(1)
+                        .and_then(|index| password.chars().nth(index))
+                        .ok_or_else(|| {
+                            SolveError::invalid("position", &position.to_string()).on_line(index)
+                        })
+                };
+
+                let password_char_at_first_pos = password_char_at(policy.min)?;
+                let password_char_at_last_pos = password_char_at(policy.max)?;
+
+                Ok((password_char_at_first_pos.to_string() == policy.letter)
+                    ^ (password_char_at_last_pos.to_string() == policy.letter))
+            })
+            .collect::<Result<Vec<bool>, SolveError>>()?;
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "1-3 a: abcde
-1-3 b: cdefg
-2-9 c: ccccccccc",
-        );
-        assert_eq!((Day02 {}).part_two(&input), "1");
+        Ok(valid.iter().filter(|&&valid| valid).count().into())
     }
+}
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/02.txt").expect("Could not read input file");
-        assert_eq!((Day02 {}).part_two(&input), "245");
+register!(
+    2020,
+    2,
+    Day02 {},
+    Puzzle {
+        title: "Password Philosophy",
+        tags: &["parsing", "validation"],
+        description: None,
     }
-}
+);
@@ -1,116 +1,178 @@
-use crate::AdventOfCode;
+use crate::utils::{Coordinate, Grid};
+use crate::visualize::{self, Colour, Draw};
+use crate::{register, Answer, Context, Frame, Param, Puzzle, Solution, SolveError};
+
+const SLOPE: Param = Param {
+    name: "slope",
+    default: "3,1",
+    description: "The slope taken in part one, as <right>,<down>",
+};
+
+const SLOPES: Param = Param {
+    name: "slopes",
+    default: "1,1 3,1 5,1 7,1 1,2",
+    description: "The slopes taken in part two, separated by spaces",
+};
+
+/// A square of the map, as drawn with the path of the toboggan on top of it
+#[derive(Clone, Copy)]
+enum Square {
+    Open,
+    Tree,
+    Passed,
+    Hit,
+}
+
+impl Draw for Square {
+    fn glyph(&self) -> char {
+        match *self {
+            Square::Open => '.',
+            Square::Tree => '#',
+            Square::Passed => 'O',
+            Square::Hit => 'X',
+        }
+    }
+
+    fn colour(&self) -> Colour {
+        match *self {
+            Square::Open => Colour::rgb(235, 240, 245),
+            Square::Tree => Colour::rgb(40, 120, 60),
+            Square::Passed => Colour::rgb(70, 110, 220),
+            Square::Hit => Colour::rgb(220, 50, 50),
+        }
+    }
+}
 
 pub struct Day03 {}
 
-impl AdventOfCode for Day03 {
-    fn part_one(&self, input: &str) -> String {
-        input
-            .lines()
-            .fold((0, 0), |(horizontal_steps, passed_trees), line: &str| {
-                let wrapped_position = (line.len() + horizontal_steps) % line.len();
-
-                (
-                    horizontal_steps + 3,
-                    if line.chars().nth(wrapped_position).unwrap() == '#' {
-                        passed_trees + 1
-                    } else {
-                        passed_trees
-                    },
-                )
-            })
-            .1
-            .to_string()
+impl Day03 {
+    /// Every position on the way down the map, with the given slope, until the bottom is reached
+    fn path(map: &Grid<bool>, slope: Coordinate) -> impl Iterator<Item = Coordinate> {
+        let height = map.height() as isize;
+
+        std::iter::successors(Some(Coordinate::default()), move |&position| {
+            Some(position + slope)
+        })
+        .take_while(move |position| position.y < height)
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-
-        slopes
-            .iter()
-            .map(|(horizontal_steps, vertical_steps)| {
-                input
-                    .lines()
-                    .enumerate()
-                    .filter_map(
-                        |(index, value)| match (vertical_steps + index) % vertical_steps {
-                            0 => Some(value),
-                            _ => None,
-                        },
-                    )
-                    .fold(
-                        (0, 0),
-                        |(horizontal_steps_taken, passed_trees), line: &str| {
-                            let wrapped_position =
-                                (line.len() + horizontal_steps_taken) % line.len();
-
-                            (
-                                horizontal_steps_taken + horizontal_steps,
-                                if line.chars().nth(wrapped_position).unwrap() == '#' {
-                                    passed_trees + 1
-                                } else {
-                                    passed_trees
-                                },
-                            )
-                        },
-                    )
-                    .1
-            })
-            .product::<usize>()
-            .to_string()
+    /// Counts the trees passed on the way down the map, with the given slope. The map repeats to
+    /// the right, which the wrapping access takes care of
+    fn count_trees(map: &Grid<bool>, slope: Coordinate) -> usize {
+        Self::path(map, slope)
+            .filter(|&position| *map.get_wrapping(position))
+            .count()
+    }
+
+    /// The map without a path on it
+    fn squares(map: &Grid<bool>) -> Grid<Square> {
+        map.map(|_, &tree| if tree { Square::Tree } else { Square::Open })
+    }
+
+    /// Marks a position of the path on the map, wrapped around onto the part of the map that's
+    /// given, as the map repeats to the right
+    fn pass(squares: &mut Grid<Square>, position: Coordinate) {
+        let x = position.x.rem_euclid(squares.width() as isize);
+
+        if let Some(square) = squares.get_mut(Coordinate::new(x, position.y)) {
+            *square = match *square {
+                Square::Tree | Square::Hit => Square::Hit,
+                Square::Open | Square::Passed => Square::Passed,
+            };
+        }
+    }
+
+    /// Parses a slope of a parameter, given as <right>,<down>
+    fn slope(param: &Param, slope: &str) -> Result<Coordinate, SolveError> {
+        let (horizontal_steps, vertical_steps) = slope
+            .split_once(',')
+            .ok_or_else(|| SolveError::invalid(param.name, slope))?;
+
+        match (horizontal_steps.parse(), vertical_steps.parse()) {
+            (Ok(horizontal_steps), Ok(vertical_steps)) if vertical_steps > 0 => {
+                Ok(Coordinate::new(horizontal_steps, vertical_steps))
+            }
+            _ => Err(SolveError::invalid(param.name, slope)),
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "..##.......
-#...#...#..
-.#....#..#.
-..#.#...#.#
-.#...##..#.
-..#.##.....
-.#.#.#....#
-.#........#
-#.##...#...
-#...##....#
-.#..#...#.#",
-        );
-        assert_eq!((Day03 {}).part_one(&input), "7");
+impl Solution for Day03 {
+    type Parsed = Grid<bool>;
+
+    fn params(&self) -> &'static [Param] {
+        &[SLOPE, SLOPES]
     }
 
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/03.txt").expect("Could not read input file");
-        assert_eq!((Day03 {}).part_one(&input), "270");
+    fn parse(&self, input: &str) -> Result<Grid<bool>, SolveError> {
+        Grid::parse(input, |c| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => Err(SolveError::invalid("map cell", &c.to_string())),
+        })
     }
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "..##.......
-#...#...#..
-.#....#..#.
-..#.#...#.#
-.#...##..#.
-..#.##.....
-.#.#.#....#
-.#........#
-#.##...#...
-#...##....#
-.#..#...#.#",
-        );
-        assert_eq!((Day03 {}).part_two(&input), "336");
+    fn part_one(&self, map: &Grid<bool>, ctx: &Context) -> Result<Answer, SolveError> {
+        let slope = ctx.param::<String>(&SLOPE)?;
+        let passed_trees = Self::count_trees(map, Self::slope(&SLOPE, &slope)?);
+
+        Ok(passed_trees.into())
     }
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/03.txt").expect("Could not read input file");
-        assert_eq!((Day03 {}).part_two(&input), "2122848000");
+    fn part_two(&self, map: &Grid<bool>, ctx: &Context) -> Result<Answer, SolveError> {
+        let slopes = ctx.param::<String>(&SLOPES)?;
+
+        let product = slopes
+            .split_whitespace()
+            .map(|slope| Ok(Self::count_trees(map, Self::slope(&SLOPES, slope)?)))
+            .product::<Result<usize, SolveError>>()?;
+
+        Ok(product.into())
+    }
+
+    /// A frame per step down the map in part one, and a frame per slope with its whole path in
+    /// part two
+    fn frames(
+        &self,
+        map: &Grid<bool>,
+        part: usize,
+        ctx: &Context,
+    ) -> Result<Vec<Frame>, SolveError> {
+        if part == 1 {
+            let slope = Self::slope(&SLOPE, &ctx.param::<String>(&SLOPE)?)?;
+            let mut squares = Self::squares(map);
+
+            return Ok(Self::path(map, slope)
+                .map(|position| {
+                    Self::pass(&mut squares, position);
+
+                    visualize::frame(&squares)
+                })
+                .collect());
+        }
+
+        ctx.param::<String>(&SLOPES)?
+            .split_whitespace()
+            .map(|slope| {
+                let mut squares = Self::squares(map);
+
+                for position in Self::path(map, Self::slope(&SLOPES, slope)?) {
+                    Self::pass(&mut squares, position);
+                }
+
+                Ok(visualize::frame(&squares))
+            })
+            .collect()
     }
 }
+
+register!(
+    2020,
+    3,
+    Day03 {},
+    Puzzle {
+        title: "Toboggan Trajectory",
+        tags: &["grid"],
+        description: Some("Steps down the map along each slope, wrapping around horizontally"),
+    }
+);
@@ -1,28 +1,20 @@
-use crate::AdventOfCode;
+use std::collections::HashMap;
+
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day04 {}
 
+type Passport = HashMap<String, String>;
+
 struct Parser<'a> {
     field: &'a str,
     rules: Vec<Box<dyn Validate>>,
 }
 
 impl<'a> Parser<'a> {
-    fn parse(&self, input: &'a str) -> bool {
-        match input
-            .split_whitespace()
-            .into_iter()
-            .find(|&key| key.contains(self.field))
-        {
-            Some(set) => {
-                let value = set
-                    .split(':')
-                    .into_iter()
-                    .nth(1)
-                    .expect("Key doesn't have a value");
-
-                self.rules.iter().any(|rule| rule.validate(value))
-            }
+    fn parse(&self, passport: &Passport) -> bool {
+        match passport.get(self.field) {
+            Some(value) => self.rules.iter().any(|rule| rule.validate(value)),
             None => false,
         }
     }
@@ -39,9 +31,11 @@ struct Range {
 
 impl Validate for Range {
     fn validate(&self, input: &str) -> bool {
-        let number = input.parse::<usize>().expect("Not a number");
-
-        number >= self.min && number <= self.max
+        // Values that aren't numbers are simply invalid, as it's what the passports are validated for
+        match input.parse::<usize>() {
+            Ok(number) => number >= self.min && number <= self.max,
+            Err(_) => false,
+        }
     }
 }
 
@@ -95,28 +89,47 @@ impl<'a> Validate for OneOf<'a> {
     }
 }
 
-impl AdventOfCode for Day04 {
-    fn part_one(&self, input: &str) -> String {
-        let required_properties = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+impl Solution for Day04 {
+    type Parsed = Vec<Passport>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Passport>, SolveError> {
+        input
+            .split("\n\n")
+            .map(|passport| {
+                passport
+                    .split_whitespace()
+                    .map(|field| {
+                        field
+                            .split_once(':')
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .ok_or_else(|| SolveError::invalid("field", field).within(input, field))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
 
-        let fields = input.split("\n\n");
+    fn part_one(&self, passports: &Vec<Passport>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let required_properties = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
 
-        fields
+        let valid_passports = passports
+            .iter()
             .fold(0, |number_of_valid_passports, passport| {
                 if required_properties
                     .iter()
-                    .all(|&property| passport.contains(property))
+                    .all(|&property| passport.contains_key(property))
                 {
                     number_of_valid_passports + 1
                 } else {
                     number_of_valid_passports
                 }
-            })
-            .to_string()
+            });
+
+        Ok(valid_passports.into())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let required_properties = vec![
+    fn part_two(&self, passports: &Vec<Passport>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let required_properties = [
             (Parser {
                 field: "byr",
                 rules: vec![Box::new(Range {
@@ -173,94 +186,28 @@ impl AdventOfCode for Day04 {
             }),
         ];
 
-        let fields = input.split("\n\n");
-
-        fields
-            .fold(0, |number_of_valid_passports, passport| {
-                if required_properties
+        let valid_passports = passports
+            .iter()
+            .filter(|passport| {
+                required_properties
                     .iter()
                     .all(|parser| parser.parse(passport))
-                {
-                    number_of_valid_passports + 1
-                } else {
-                    number_of_valid_passports
-                }
             })
-            .to_string()
-    }
-}
+            .count();
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-byr:1937 iyr:2017 cid:147 hgt:183cm
-
-iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-hcl:#cfa07d byr:1929
-
-hcl:#ae17e1 iyr:2013
-eyr:2024
-ecl:brn pid:760753108 byr:1931
-hgt:179cm
-
-hcl:#cfa07d eyr:2025 pid:166559648
-iyr:2011 ecl:brn hgt:59in",
-        );
-        assert_eq!((Day04 {}).part_one(&input), "2");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/04.txt").expect("Could not read input file");
-        assert_eq!((Day04 {}).part_one(&input), "264");
-    }
-
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "eyr:1972 cid:100
-hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
-
-iyr:2019
-hcl:#602927 eyr:1967 hgt:170cm
-ecl:grn pid:012533040 byr:1946
-
-hcl:dab227 iyr:2012
-ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
-
-hgt:59cm ecl:zzz
-eyr:2038 hcl:74454a iyr:2023
-pid:3556412378 byr:2007",
-        );
-        assert_eq!((Day04 {}).part_two(&input), "0");
-
-        let input2 = String::from(
-            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
-hcl:#623a2f
-
-eyr:2029 ecl:blu cid:129 byr:1989
-iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
-
-hcl:#888785
-hgt:164cm byr:2001 iyr:2015 cid:88
-pid:545766238 ecl:hzl
-eyr:2022
-
-iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
-        );
-        assert_eq!((Day04 {}).part_two(&input2), "4");
+        Ok(valid_passports.into())
     }
+}
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/04.txt").expect("Could not read input file");
-        assert_eq!((Day04 {}).part_two(&input), "224");
+register!(
+    2020,
+    4,
+    Day04 {},
+    Puzzle {
+        title: "Passport Processing",
+        tags: &["parsing", "validation"],
+        description: Some(
+            "Validates each field with a composable rule, such as a range with a unit suffix"
+        ),
     }
-}
+);
@@ -1,4 +1,5 @@
-use crate::AdventOfCode;
+use crate::error::parse_lines;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day05 {}
 
@@ -23,29 +24,43 @@ impl Seat {
             .0
     }
 
-    fn calculate_id(sequence: &str) -> usize {
+    fn calculate_id(sequence: &str) -> Result<usize, SolveError> {
+        let valid = sequence.len() == 10
+            && sequence[..7]
+                .chars()
+                .all(|letter| letter == 'F' || letter == 'B')
+            && sequence[7..]
+                .chars()
+                .all(|letter| letter == 'L' || letter == 'R');
+
+        if !valid {
+            return Err(SolveError::invalid("boarding pass", sequence));
+        }
+
         let row = Self::search_for_pos(&sequence[..7], 7);
         let column = Self::search_for_pos(&sequence[7..10], 3);
 
-        row * 8 + column
+        Ok(row * 8 + column)
     }
 }
 
-impl AdventOfCode for Day05 {
-    fn part_one(&self, input: &str) -> String {
-        input
-            .lines()
-            .map(|line| Seat::calculate_id(line))
+impl Solution for Day05 {
+    type Parsed = Vec<usize>;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, SolveError> {
+        parse_lines(input, Seat::calculate_id)
+    }
+
+    fn part_one(&self, seats: &Vec<usize>, _ctx: &Context) -> Result<Answer, SolveError> {
+        seats
+            .iter()
             .max()
-            .expect("No seats found")
-            .to_string()
+            .ok_or_else(|| SolveError::no_solution("no seats found"))
+            .map(|&seat| Answer::from(seat))
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let mut seats = input
-            .lines()
-            .map(|line| Seat::calculate_id(line))
-            .collect::<Vec<usize>>();
+    fn part_two(&self, seats: &Vec<usize>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let mut seats = seats.clone();
 
         seats.sort_unstable();
 
@@ -58,40 +73,33 @@ impl AdventOfCode for Day05 {
                     None
                 }
             })
-            .expect("Didn't find any leftover seat")
-            .to_string()
+            .ok_or_else(|| SolveError::no_solution("didn't find any leftover seat"))
+            .map(Answer::from)
     }
 }
 
+register!(
+    2020,
+    5,
+    Day05 {},
+    Puzzle {
+        title: "Binary Boarding",
+        tags: &["binary space partitioning"],
+        description: Some("Narrows the row and column down by halving the range for each letter"),
+    }
+);
+
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
-
     use super::*;
 
     #[test]
     fn test_generate_seat_id_examples() {
-        assert_eq!(Seat::calculate_id("FBFBBFFRLR"), 357);
-        assert_eq!(Seat::calculate_id("BFFFBBFRRR"), 567);
-        assert_eq!(Seat::calculate_id("FFFBBBFRRR"), 119);
-        assert_eq!(Seat::calculate_id("BBFFBBFRLL"), 820);
-    }
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from("FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL");
-        assert_eq!((Day05 {}).part_one(&input), "820");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/05.txt").expect("Could not read input file");
-        assert_eq!((Day05 {}).part_one(&input), "915");
-    }
-
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/05.txt").expect("Could not read input file");
-        assert_eq!((Day05 {}).part_two(&input), "699");
+        assert_eq!(Seat::calculate_id("FBFBBFFRLR"), Ok(357));
+        assert_eq!(Seat::calculate_id("BFFFBBFRRR"), Ok(567));
+        assert_eq!(Seat::calculate_id("FFFBBBFRRR"), Ok(119));
+        assert_eq!(Seat::calculate_id("BBFFBBFRLL"), Ok(820));
+        assert!(Seat::calculate_id("FBFBBFFRL").is_err());
+        assert!(Seat::calculate_id("FBFBBFFRLX").is_err());
     }
 }
@@ -1,105 +1,70 @@
 use std::collections::HashSet;
 
-use crate::AdventOfCode;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day06 {}
 
-impl AdventOfCode for Day06 {
-    fn part_one(&self, input: &str) -> String {
-        input
+impl Solution for Day06 {
+    /// The answers of every person, grouped together
+    type Parsed = Vec<Vec<HashSet<char>>>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<HashSet<char>>>, SolveError> {
+        Ok(input
             .split("\n\n")
             .map(|group| {
                 group
                     .lines()
-                    .flat_map(|answers| answers.chars())
-                    .collect::<HashSet<char>>()
-                    .len()
+                    .map(|answers| answers.chars().collect::<HashSet<char>>())
+                    .collect()
             })
-            .sum::<usize>()
-            .to_string()
+            .collect())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        input
-            .split("\n\n")
+    fn part_one(
+        &self,
+        groups: &Vec<Vec<HashSet<char>>>,
+        _ctx: &Context,
+    ) -> Result<Answer, SolveError> {
+        let yes_answers = groups
+            .iter()
+            .map(|group| group.iter().flatten().collect::<HashSet<&char>>().len())
+            .sum::<usize>();
+
+        Ok(yes_answers.into())
+    }
+
+    fn part_two(
+        &self,
+        groups: &Vec<Vec<HashSet<char>>>,
+        _ctx: &Context,
+    ) -> Result<Answer, SolveError> {
+        let yes_answers = groups
+            .iter()
             .map(|group| {
-                let all_group_answers = group
-                    .lines()
-                    .flat_map(|answers| answers.chars())
-                    .collect::<HashSet<char>>();
+                let all_group_answers = group.iter().flatten().cloned().collect::<HashSet<char>>();
 
                 group
-                    .lines()
-                    .map(|answers| answers.chars().collect::<HashSet<char>>())
+                    .iter()
                     .fold(all_group_answers, |answers, group| {
-                        answers.intersection(&group).cloned().collect()
+                        answers.intersection(group).cloned().collect()
                     })
                     .len()
             })
-            .sum::<usize>()
-            .to_string()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "abc
+            .sum::<usize>();
 
-a
-b
-c
-
-ab
-ac
-
-a
-a
-a
-a
-
-b",
-        );
-        assert_eq!((Day06 {}).part_one(&input), "11");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/06.txt").expect("Could not read input file");
-        assert_eq!((Day06 {}).part_one(&input), "6782");
-    }
-
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "abc
-
-a
-b
-c
-
-ab
-ac
-
-a
-a
-a
-a
-
-b",
-        );
-        assert_eq!((Day06 {}).part_two(&input), "6");
+        Ok(yes_answers.into())
     }
+}
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/06.txt").expect("Could not read input file");
-        assert_eq!((Day06 {}).part_two(&input), "3596");
+register!(
+    2020,
+    6,
+    Day06 {},
+    Puzzle {
+        title: "Custom Customs",
+        tags: &["sets"],
+        description: Some(
+            "Takes the union of each group's answers in part one, and the intersection in part two"
+        ),
     }
-}
+);
@@ -1,54 +1,65 @@
 use std::collections::HashMap;
 
-use crate::AdventOfCode;
+use crate::error::{parse_lines, parse_number};
+use crate::{register, Answer, Context, Param, Puzzle, Solution, SolveError};
+
+const BAG: Param = Param {
+    name: "bag",
+    default: "shiny gold",
+    description: "The bag to find the containing and contained bags of",
+};
 
 type Bag = HashMap<String, usize>;
 type Bags = HashMap<String, Bag>;
-struct BagContainer {
+pub struct BagContainer {
     bags: Bags,
 }
 
 impl BagContainer {
-    fn parse(input: &str) -> Self {
-        let container = input
-            .lines()
-            .map(|line| {
-                let mut bag_rules = line.split(" bags contain ");
-
-                let outer_bag = bag_rules
-                    .next()
-                    .expect("Didnt find an outer bag")
-                    .to_string();
-
-                let contain_map = bag_rules
-                    .next()
-                    .expect("Didnt find any rules")
-                    .split(", ")
-                    .filter_map(|rule| {
-                        let count = rule.split(' ').next().expect("No quantity provided");
-
-                        let quantity = match count {
-                            "no" => None,
-                            number => number.parse::<usize>().ok(),
-                        }?;
-
-                        let remaining_traits = &rule[count.len() + 1..];
-
-                        let bag_traits = remaining_traits
-                            .rsplitn(2, ' ')
-                            .nth(1)
-                            .expect("No traits found")
-                            .to_string();
-
-                        (bag_traits, quantity).into()
-                    })
-                    .collect::<Bag>();
-
-                (outer_bag, contain_map)
-            })
-            .collect::<Bags>();
-
-        Self { bags: container }
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let container = parse_lines(input, |line| {
+            let (outer_bag, rules) = line
+                .split_once(" bags contain ")
+                .ok_or_else(|| SolveError::invalid("bag rule", line))?;
+
+            let contain_map = rules
+                .split(", ")
+                .map(|rule| {
+                    let (count, remaining_traits) = rule
+                        .split_once(' ')
+                        .ok_or_else(|| SolveError::missing("quantity").with_text(rule))?;
+
+                    let quantity = match count {
+                        "no" => return Ok(None),
+                        number => parse_number::<usize>(number)?,
+                    };
+
+                    let bag_traits = remaining_traits
+                        .rsplit_once(' ')
+                        .map(|(traits, _)| traits)
+                        .ok_or_else(|| SolveError::missing("traits").with_text(rule))?
+                        .to_string();
+
+                    Ok(Some((bag_traits, quantity)))
+                })
+                .filter_map(Result::transpose)
+                .collect::<Result<Bag, SolveError>>()?;
+
+            Ok((outer_bag.to_string(), contain_map))
+        })?
+        .into_iter()
+        .collect::<Bags>();
+
+        // Every bag that is contained in another needs rules of its own
+        if let Some(bag_trait) = container
+            .values()
+            .flat_map(|bag| bag.keys())
+            .find(|&bag_trait| !container.contains_key(bag_trait))
+        {
+            return Err(SolveError::missing("bag rule").with_text(bag_trait));
+        }
+
+        Ok(Self { bags: container })
     }
 
     fn contains_bag(&self, search_trait: &str, bag_trait: &str) -> bool {
@@ -75,74 +86,41 @@ impl BagContainer {
 
 pub struct Day07 {}
 
-impl AdventOfCode for Day07 {
-    fn part_one(&self, input: &str) -> String {
-        (BagContainer::parse(input).get_outer_bags_containing("shiny gold")).to_string()
-    }
+impl Solution for Day07 {
+    type Parsed = BagContainer;
 
-    fn part_two(&self, input: &str) -> String {
-        (BagContainer::parse(input).get_number_of_bags("shiny gold") - 1).to_string()
+    fn params(&self) -> &'static [Param] {
+        &[BAG]
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags.",
-        );
-        assert_eq!((Day07 {}).part_one(&input), "4");
+    fn parse(&self, input: &str) -> Result<BagContainer, SolveError> {
+        BagContainer::parse(input)
     }
 
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/07.txt").expect("Could not read input file");
-        assert_eq!((Day07 {}).part_one(&input), "131");
-    }
+    fn part_one(&self, container: &BagContainer, ctx: &Context) -> Result<Answer, SolveError> {
+        let bag = ctx.param::<String>(&BAG)?;
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags.",
-        );
-        assert_eq!((Day07 {}).part_two(&input), "32");
-
-        let long_topology_input = String::from(
-            "shiny gold bags contain 2 dark red bags.
-dark red bags contain 2 dark orange bags.
-dark orange bags contain 2 dark yellow bags.
-dark yellow bags contain 2 dark green bags.
-dark green bags contain 2 dark blue bags.
-dark blue bags contain 2 dark violet bags.
-dark violet bags contain no other bags.",
-        );
-        assert_eq!((Day07 {}).part_two(&long_topology_input), "126");
+        Ok(container.get_outer_bags_containing(&bag).into())
     }
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/07.txt").expect("Could not read input file");
-        assert_eq!((Day07 {}).part_two(&input), "11261");
+    fn part_two(&self, container: &BagContainer, ctx: &Context) -> Result<Answer, SolveError> {
+        let bag = ctx.param::<String>(&BAG)?;
+
+        if !container.bags.contains_key(&bag) {
+            return Err(SolveError::no_solution("no rules for the bag").with_text(&bag));
+        }
+
+        Ok((container.get_number_of_bags(&bag) - 1).into())
     }
 }
+
+register!(
+    2020,
+    7,
+    Day07 {},
+    Puzzle {
+        title: "Handy Haversacks",
+        tags: &["graph", "recursion"],
+        description: Some("Walks the rules up for containing bags, and down for contained bags"),
+    }
+);
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
-use crate::AdventOfCode;
+use crate::error::{parse_lines, parse_number};
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 enum Instruction {
     Acc(isize),
     Jmp(isize),
@@ -11,38 +12,38 @@ enum Instruction {
 
 type BootCode = Vec<Instruction>;
 
-#[derive(Clone, Default)]
-struct Device {
+#[derive(Clone, Debug, Default)]
+pub struct Device {
     boot_code: BootCode,
     pc: usize,
     accumulator: isize,
 }
 
 impl Device {
-    fn from_string(input: &str) -> Self {
-        Self {
-            boot_code: input
-                .lines()
-                .map(|line| {
-                    let mut instruction = line.splitn(2, ' ');
-
-                    let operation = instruction.next().expect("No operation found");
-                    let argument = instruction
-                        .next()
-                        .expect("No argument found")
-                        .parse::<isize>()
-                        .expect("Argument not a number");
-
-                    match operation {
-                        "acc" => Instruction::Acc(argument),
-                        "jmp" => Instruction::Jmp(argument),
-                        "nop" => Instruction::Nop(argument),
-                        _ => panic!("Invalid operation"),
-                    }
-                })
-                .collect(),
-            ..Default::default()
+    fn from_string(input: &str) -> Result<Self, SolveError> {
+        let boot_code = parse_lines(input, |line| {
+            let (operation, argument) = line
+                .split_once(' ')
+                .ok_or_else(|| SolveError::missing("argument").with_text(line))?;
+
+            let argument = parse_number::<isize>(argument)?;
+
+            match operation {
+                "acc" => Ok(Instruction::Acc(argument)),
+                "jmp" => Ok(Instruction::Jmp(argument)),
+                "nop" => Ok(Instruction::Nop(argument)),
+                _ => Err(SolveError::invalid("operation", operation)),
+            }
+        })?;
+
+        if boot_code.is_empty() {
+            return Err(SolveError::missing("boot code"));
         }
+
+        Ok(Self {
+            boot_code,
+            ..Default::default()
+        })
     }
 
     fn run_instruction(&mut self) {
@@ -78,28 +79,29 @@ impl Device {
 
 pub struct Day08 {}
 
-impl AdventOfCode for Day08 {
-    fn part_one(&self, input: &str) -> String {
-        let mut device = Device::from_string(input);
+impl Solution for Day08 {
+    type Parsed = Device;
+
+    fn parse(&self, input: &str) -> Result<Device, SolveError> {
+        Device::from_string(input)
+    }
+
+    fn part_one(&self, device: &Device, _ctx: &Context) -> Result<Answer, SolveError> {
+        let mut device = device.clone();
 
         match device.run() {
-            Err(_) => device.accumulator,
-            _ => panic!("Didn't run forever"),
+            Err(_) => Ok(device.accumulator.into()),
+            _ => Err(SolveError::no_solution("the boot code didn't run forever")),
         }
-        .to_string()
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let device = Device::from_string(input);
-
+    fn part_two(&self, device: &Device, _ctx: &Context) -> Result<Answer, SolveError> {
         device
             .boot_code
             .iter()
             .enumerate()
-            .filter(|(_, &instruction)| match instruction {
-                Instruction::Jmp(_) => true,
-                Instruction::Nop(_) => true,
-                _ => false,
+            .filter(|(_, &instruction)| {
+                matches!(instruction, Instruction::Jmp(_) | Instruction::Nop(_))
             })
             .find_map(|(pc, &instruction)| {
                 let mut new_device = device.clone();
@@ -115,58 +117,36 @@ impl AdventOfCode for Day08 {
                     Err(()) => None,
                 }
             })
-            .expect("No operation replacement resulted in a succesful run")
-            .to_string()
+            .ok_or_else(|| {
+                SolveError::no_solution("no operation replacement resulted in a succesful run")
+            })
+            .map(Answer::from)
     }
 }
 
+register!(
+    2020,
+    8,
+    Day08 {},
+    Puzzle {
+        title: "Handheld Halting",
+        tags: &["interpreter", "cycle detection"],
+        description: Some("Runs until an instruction repeats, swapping each jmp and nop to fix it"),
+    }
+);
+
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
-
     use super::*;
+    use crate::ErrorKind;
 
     #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "nop +0
-acc +1
-jmp +4
-acc +3
-jmp -3
-acc -99
-acc +1
-jmp -4
-acc +6",
-        );
-        assert_eq!((Day08 {}).part_one(&input), "5");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/08.txt").expect("Could not read input file");
-        assert_eq!((Day08 {}).part_one(&input), "1600");
-    }
+    fn test_invalid_operation() {
+        let input = String::from("nop +0\nacc +1\nmul +4");
+        let error = (Day08 {}).parse(&input).unwrap_err();
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "nop +0
-acc +1
-jmp +4
-acc +3
-jmp -3
-acc -99
-acc +1
-jmp -4
-acc +6",
-        );
-        assert_eq!((Day08 {}).part_two(&input), "8");
-    }
-
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/08.txt").expect("Could not read input file");
-        assert_eq!((Day08 {}).part_two(&input), "1543");
+        assert_eq!(error.kind, ErrorKind::Invalid("operation"));
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.text, Some(String::from("mul")));
     }
 }
@@ -1,16 +1,17 @@
-use crate::AdventOfCode;
+use crate::error::{parse_lines, parse_number};
+use crate::{register, Answer, Context, Param, Puzzle, Solution, SolveError};
 
-struct XMAS {
-    preamble_size: usize,
-}
+const PREAMBLE: Param = Param {
+    name: "preamble",
+    default: "25",
+    description: "The number of preceding numbers each number has to be a sum of two of",
+};
 
-impl Default for XMAS {
-    fn default() -> Self {
-        Self { preamble_size: 25 }
-    }
+struct Xmas {
+    preamble_size: usize,
 }
 
-impl XMAS {
+impl Xmas {
     fn find_invalid_number(&self, list: &[usize]) -> Option<usize> {
         list.iter().enumerate().skip(self.preamble_size).find_map(
             |(next_number_index, &next_number)| {
@@ -68,116 +69,45 @@ impl XMAS {
 
 pub struct Day09;
 
-impl Day09 {
-    fn parse(input: &str) -> Vec<usize> {
-        input
-            .lines()
-            .map(|x| x.parse::<usize>().expect("Line was not a number"))
-            .collect()
-    }
-}
-
-impl AdventOfCode for Day09 {
-    fn part_one(&self, input: &str) -> String {
-        let xmas = XMAS::default();
-
-        let collection = Self::parse(input);
+impl Solution for Day09 {
+    type Parsed = Vec<usize>;
 
-        xmas.find_invalid_number(&collection)
-            .expect("No invalid number found")
-            .to_string()
+    fn params(&self) -> &'static [Param] {
+        &[PREAMBLE]
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let xmas = XMAS::default();
-
-        let collection = Self::parse(input);
-
-        xmas.find_encryption_weakness(&collection)
-            .expect("No encryption weakness found")
-            .to_string()
+    fn parse(&self, input: &str) -> Result<Vec<usize>, SolveError> {
+        parse_lines(input, parse_number::<usize>)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "35
-20
-15
-25
-47
-40
-62
-55
-65
-95
-102
-117
-150
-182
-127
-219
-299
-277
-309
-576",
-        );
-
-        let collection = Day09::parse(&input);
-
-        let xmas = XMAS { preamble_size: 5 };
-
-        assert_eq!(xmas.find_invalid_number(&collection), Some(127));
-    }
+    fn part_one(&self, collection: &Vec<usize>, ctx: &Context) -> Result<Answer, SolveError> {
+        let xmas = Xmas {
+            preamble_size: ctx.param(&PREAMBLE)?,
+        };
 
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/09.txt").expect("Could not read input file");
-        assert_eq!((Day09 {}).part_one(&input), "85848519");
+        xmas.find_invalid_number(collection)
+            .ok_or_else(|| SolveError::no_solution("no invalid number found"))
+            .map(Answer::from)
     }
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "35
-20
-15
-25
-47
-40
-62
-55
-65
-95
-102
-117
-150
-182
-127
-219
-299
-277
-309
-576",
-        );
-
-        let collection = Day09::parse(&input);
-
-        let xmas = XMAS { preamble_size: 5 };
-
-        assert_eq!(xmas.find_encryption_weakness(&collection), Some(62));
-    }
+    fn part_two(&self, collection: &Vec<usize>, ctx: &Context) -> Result<Answer, SolveError> {
+        let xmas = Xmas {
+            preamble_size: ctx.param(&PREAMBLE)?,
+        };
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/09.txt").expect("Could not read input file");
-        assert_eq!((Day09 {}).part_two(&input), "13414198");
+        xmas.find_encryption_weakness(collection)
+            .ok_or_else(|| SolveError::no_solution("no encryption weakness found"))
+            .map(Answer::from)
     }
 }
+
+register!(
+    2020,
+    9,
+    Day09 {},
+    Puzzle {
+        title: "Encoding Error",
+        tags: &["sliding window", "two sum"],
+        description: Some("Pair sums over the preamble, then a growing contiguous range"),
+    }
+);
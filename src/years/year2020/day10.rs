@@ -1,16 +1,24 @@
-use crate::AdventOfCode;
+use crate::error::{parse_lines, parse_number};
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day10 {}
 
-impl AdventOfCode for Day10 {
-    fn part_one(&self, input: &str) -> String {
-        let mut adapters: Vec<usize> = input
-            .lines()
-            .map(|line| line.parse::<usize>().expect("Not a number"))
-            .collect();
+impl Solution for Day10 {
+    /// The adapters, sorted by their joltage
+    type Parsed = Vec<usize>;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, SolveError> {
+        let mut adapters = parse_lines(input, parse_number::<usize>)?;
 
-        adapters.push(0);
         adapters.sort_unstable();
+
+        Ok(adapters)
+    }
+
+    fn part_one(&self, adapters: &Vec<usize>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let mut adapters = adapters.clone();
+
+        adapters.insert(0, 0);
         adapters.push(adapters.last().unwrap() + 3);
 
         let differences = adapters.windows(2).fold((0, 0), |(ones, threes), window| {
@@ -21,17 +29,10 @@ impl AdventOfCode for Day10 {
             }
         });
 
-        (differences.0 * differences.1).to_string()
+        Ok((differences.0 * differences.1).into())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let mut adapters: Vec<usize> = input
-            .lines()
-            .map(|line| line.parse::<usize>().expect("Not a number"))
-            .collect();
-
-        adapters.sort_unstable();
-
+    fn part_two(&self, adapters: &Vec<usize>, _ctx: &Context) -> Result<Answer, SolveError> {
         let mut memory = Vec::with_capacity(adapters.len() + 1);
 
         // Prefill our memory, just to get going. There's at least one viable path
@@ -54,131 +55,19 @@ impl AdventOfCode for Day10 {
             memory.push((adapter, paths));
         });
 
-        memory.last().unwrap().1.to_string()
+        Ok(memory.last().unwrap().1.into())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "16
-10
-15
-5
-1
-11
-7
-19
-6
-12
-4",
-        );
-        assert_eq!((Day10 {}).part_one(&input), (5 * 7).to_string());
-
-        let input = String::from(
-            "28
-33
-18
-42
-31
-14
-46
-20
-48
-47
-24
-23
-49
-45
-19
-38
-39
-11
-1
-32
-25
-35
-8
-17
-7
-9
-4
-2
-34
-10
-3",
-        );
-        assert_eq!((Day10 {}).part_one(&input), (22 * 10).to_string());
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/10.txt").expect("Could not read input file");
-        assert_eq!((Day10 {}).part_one(&input), "2592");
+register!(
+    2020,
+    10,
+    Day10 {},
+    Puzzle {
+        title: "Adapter Array",
+        tags: &["DP"],
+        description: Some(
+            "Counts the arrangements as the sum of the arrangements of the adapters within reach"
+        ),
     }
-
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "16
-10
-15
-5
-1
-11
-7
-19
-6
-12
-4",
-        );
-        assert_eq!((Day10 {}).part_two(&input), "8");
-
-        let input = String::from(
-            "28
-33
-18
-42
-31
-14
-46
-20
-48
-47
-24
-23
-49
-45
-19
-38
-39
-11
-1
-32
-25
-35
-8
-17
-7
-9
-4
-2
-34
-10
-3",
-        );
-        assert_eq!((Day10 {}).part_two(&input), "19208");
-    }
-
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/10.txt").expect("Could not read input file");
-        assert_eq!((Day10 {}).part_two(&input), "198428693313536");
-    }
-}
+);
@@ -1,269 +1,232 @@
-use crate::AdventOfCode;
-
-#[derive(Clone, Copy, PartialEq)]
+use crate::utils::automaton::{Adjacent, LineOfSight, Neighbours, Rules};
+use crate::utils::{Automaton, Grid};
+use crate::visualize::{self, Colour, Draw};
+use crate::{register, Answer, Context, Frame, Param, Puzzle, Solution, SolveError};
+
+const ADJACENT_TOLERANCE: Param = Param {
+    name: "adjacent_tolerance",
+    default: "4",
+    description: "The number of occupied adjacent seats that makes someone leave in part one",
+};
+
+const VISIBLE_TOLERANCE: Param = Param {
+    name: "visible_tolerance",
+    default: "5",
+    description: "The number of occupied visible seats that makes someone leave in part two",
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Tile {
     Empty,
     Floor,
     Occupied,
 }
 
-impl std::fmt::Display for Tile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Draw for Tile {
+    fn glyph(&self) -> char {
         match *self {
-            Tile::Empty => write!(f, "L"),
-            Tile::Floor => write!(f, "."),
-            Tile::Occupied => write!(f, "#"),
+            Tile::Empty => 'L',
+            Tile::Floor => '.',
+            Tile::Occupied => '#',
+        }
+    }
+
+    fn colour(&self) -> Colour {
+        match *self {
+            Tile::Empty => Colour::rgb(110, 200, 110),
+            Tile::Floor => Colour::rgb(70, 70, 70),
+            Tile::Occupied => Colour::rgb(230, 90, 60),
         }
     }
 }
 
+/// Prints the same glyph as the tile is drawn with, so the printed and replayed seats look alike
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.glyph())
+    }
+}
+
 #[derive(Clone)]
-struct WaitingArea {
-    tiles: Vec<Tile>,
-    width: u8,
-    height: u8,
+pub struct WaitingArea {
+    seats: Grid<Tile>,
     include_floor: bool,
-    tolerance: u8,
+    tolerance: usize,
 }
 
 impl WaitingArea {
-    fn new(input: &str, include_floor: bool, tolerance: u8) -> Self {
-        Self {
-            tiles: input
-                .chars()
-                .filter_map(|c| match c {
-                    'L' => Some(Tile::Empty),
-                    '.' => Some(Tile::Floor),
-                    '#' => Some(Tile::Occupied),
-                    '\n' => None,
-                    l => panic!(
-                        "Input invalid, found {:?} but expected 'L', '.', '#' or '\\n'",
-                        l
-                    ),
-                })
-                .collect::<Vec<Tile>>(),
-            width: input.lines().next().unwrap().len() as u8,
-            height: input.lines().count() as u8,
+    /// Parses the seat layout. Seats look past the floor for the first visible seat unless the
+    /// floor is included, and people leave once the tolerance of occupied seats is reached
+    pub fn new(input: &str, include_floor: bool, tolerance: usize) -> Result<Self, SolveError> {
+        let seats = Grid::parse(input, |c| match c {
+            'L' => Ok(Tile::Empty),
+            '.' => Ok(Tile::Floor),
+            '#' => Ok(Tile::Occupied),
+            _ => Err(SolveError::invalid("tile", &c.to_string())),
+        })?;
+
+        Ok(Self {
+            seats,
             include_floor,
             tolerance,
-        }
+        })
     }
 
-    fn get_valid_move_paths() -> Vec<(isize, isize)> {
-        vec![
-            // Left
-            (-1, 0),
-            // Up left
-            (-1, -1),
-            // Up
-            (0, -1),
-            // Up right
-            (1, -1),
-            // Right
-            (1, 0),
-            // Down right
-            (1, 1),
-            // Down
-            (0, 1),
-            // Down left
-            (-1, 1),
-        ]
+    /// A copy of the waiting area, where people follow the given rules
+    fn with_rules(&self, include_floor: bool, tolerance: usize) -> Self {
+        Self {
+            include_floor,
+            tolerance,
+            ..self.clone()
+        }
     }
 
-    fn get_occupied_seats(&self) -> usize {
-        self.tiles
+    pub fn get_occupied_seats(&self) -> usize {
+        self.seats
             .iter()
-            .filter(|&&tile| tile == Tile::Occupied)
+            .filter(|&(_, &tile)| tile == Tile::Occupied)
             .count()
     }
 
-    fn get_adjacent_tile(
-        &self,
-        current_index: usize,
-        move_x: isize,
-        move_y: isize,
-    ) -> Option<&Tile> {
-        let index = current_index as isize;
-        let width = self.width as isize;
-        let height = self.height as isize;
-        let size = width * height;
-
-        // Calculate whether this would wrap to a new row when not only moving on the y axis
-        let invalid_move = if move_x != 0 {
-            // Ensure x axis moves doesn't wrap to another row
-            let flat_x = index % width;
-            let next_x = flat_x + move_x;
-
-            next_x < 0 || next_x >= width
-        } else {
-            false
-        };
-
-        if invalid_move {
-            return None;
-        }
-
-        let new_index = index + move_y * width + move_x;
+    /// Seats with no occupied seats around them become occupied, and seats with at least the
+    /// tolerance of occupied seats around them become empty. The seats around a seat are the ones
+    /// next to it, or the first seat seen in every direction when looking past the floor
+    fn rules(&self) -> Box<dyn Automaton<Grid<Tile>>> {
+        let tolerance = self.tolerance;
+        let transition = move |&tile: &Tile, neighbours: Neighbours<Tile>| {
+            if tile == Tile::Floor {
+                return tile;
+            }
 
-        match new_index {
-            // Only allow moves within the bounds of the grid
-            index if new_index >= 0 && new_index < size => Some(&self.tiles[index as usize]),
-            _ => None,
-        }
-    }
+            // Counting stops at the tolerance, as more occupied seats don't change anything
+            let occupied = neighbours
+                .filter(|&&neighbour| neighbour == Tile::Occupied)
+                .take(tolerance)
+                .count();
 
-    fn get_adjacent_seat(
-        &self,
-        current_index: usize,
-        move_x: isize,
-        move_y: isize,
-    ) -> Option<Tile> {
-        match self.get_adjacent_tile(current_index, move_x, move_y) {
-            Some(Tile::Floor) => {
-                if self.include_floor {
-                    Some(Tile::Floor)
-                } else {
-                    let next_x = match move_x.cmp(&0) {
-                        std::cmp::Ordering::Equal => 0,
-                        std::cmp::Ordering::Greater => move_x + 1,
-                        std::cmp::Ordering::Less => move_x - 1,
-                    };
-
-                    let next_y = match move_y.cmp(&0) {
-                        std::cmp::Ordering::Equal => 0,
-                        std::cmp::Ordering::Greater => move_y + 1,
-                        std::cmp::Ordering::Less => move_y - 1,
-                    };
-
-                    self.get_adjacent_seat(current_index, next_x, next_y)
-                }
+            match tile {
+                Tile::Empty if occupied == 0 => Tile::Occupied,
+                Tile::Occupied if occupied >= tolerance => Tile::Empty,
+                _ => tile,
             }
-            Some(tile) => Some(*tile),
-            None => None,
-        }
-    }
+        };
 
-    // If a seat is empty and there are no occupied seats adjacent
-    // to it, the seat becomes occupied
-    fn occupy_if_possible(&self, index: usize) -> Option<Tile> {
-        match Self::get_valid_move_paths().iter().find_map(|&(x, y)| {
-            match self.get_adjacent_seat(index, x, y) {
-                Some(Tile::Occupied) => Some(Tile::Occupied),
-                _ => None,
-            }
-        }) {
-            Some(_) => None,
-            None => Some(Tile::Occupied),
-        }
-    }
+        if self.include_floor {
+            Box::new(Rules::new(&self.seats, Adjacent, transition))
+        } else {
+            // The floor never changes, so the seats seen from every seat can be looked up up front
+            let floor = LineOfSight(|&tile: &Tile| tile == Tile::Floor);
 
-    // If a seat is occupied and four or more seats adjacent to it
-    // are also occupied, the seat becomes empty
-    fn leave_is_possible(&self, index: usize) -> Option<Tile> {
-        match Self::get_valid_move_paths()
-            .iter()
-            .try_fold(0, |acc, &(x, y)| {
-                match self.get_adjacent_seat(index, x, y) {
-                    Some(Tile::Occupied) => {
-                        let new_sum = acc + 1;
-                        if new_sum >= self.tolerance {
-                            Err(new_sum)
-                        } else {
-                            Ok(new_sum)
-                        }
-                    }
-                    _ => Ok(acc),
-                }
-            }) {
-            Err(_) => Some(Tile::Empty),
-            _ => None,
+            Box::new(Rules::new(&self.seats, floor, transition))
         }
     }
 
-    fn advance(&mut self) -> Result<(), ()> {
-        let mut altered = false;
-        let new_tiles = self
-            .tiles
-            .iter()
-            .enumerate()
-            .map(|(index, tile)| match tile {
-                Tile::Empty => {
-                    if let Some(occupied) = self.occupy_if_possible(index) {
-                        altered = true;
-
-                        occupied
-                    } else {
-                        *tile
-                    }
-                }
-                Tile::Occupied => {
-                    if let Some(leave) = self.leave_is_possible(index) {
-                        altered = true;
-
-                        leave
-                    } else {
-                        *tile
-                    }
-                }
-                Tile::Floor => *tile,
-            })
-            .collect();
-
-        if altered {
-            self.tiles = new_tiles;
+    /// Advances until no seat changes any more, which can take a while on a large area
+    pub fn settle(&mut self, ctx: &Context) -> Result<(), SolveError> {
+        let (seats, rounds) = self.rules().run_until_stable(self.seats.clone(), ctx)?;
 
-            Ok(())
-        } else {
-            Err(())
-        }
+        self.seats = seats;
+        ctx.log(format!("stable after {} rounds", rounds));
+
+        Ok(())
     }
 }
 
 impl std::fmt::Display for WaitingArea {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                write!(
-                    f,
-                    "{}",
-                    format!("{}", self.tiles[(y * self.width + x) as usize])
-                )?;
-            }
+        write!(f, "{}", self.seats)
+    }
+}
 
-            if y < self.height - 1 {
-                writeln!(f)?;
-            }
-        }
+pub struct Day11 {}
 
-        write!(f, "")
+impl Day11 {
+    /// A copy of the waiting area, where people follow the rules of the part
+    fn with_rules_of(
+        waiting_area: &WaitingArea,
+        part: usize,
+        ctx: &Context,
+    ) -> Result<WaitingArea, SolveError> {
+        match part {
+            1 => Ok(waiting_area.with_rules(true, ctx.param(&ADJACENT_TOLERANCE)?)),
+            2 => Ok(waiting_area.with_rules(false, ctx.param(&VISIBLE_TOLERANCE)?)),
+            _ => Err(SolveError::invalid("part", &part.to_string())),
+        }
     }
 }
 
-pub struct Day11 {}
+impl Solution for Day11 {
+    type Parsed = WaitingArea;
 
-impl AdventOfCode for Day11 {
-    fn part_one(&self, input: &str) -> String {
-        let mut waiting_area = WaitingArea::new(input, true, 4);
+    fn params(&self) -> &'static [Param] {
+        &[ADJACENT_TOLERANCE, VISIBLE_TOLERANCE]
+    }
 
-        while waiting_area.advance().is_ok() {}
+    fn parse(&self, input: &str) -> Result<WaitingArea, SolveError> {
+        WaitingArea::new(input, true, 4)
+    }
 
-        waiting_area.get_occupied_seats().to_string()
+    fn part_one(&self, waiting_area: &WaitingArea, ctx: &Context) -> Result<Answer, SolveError> {
+        let mut waiting_area = Self::with_rules_of(waiting_area, 1, ctx)?;
+        waiting_area.settle(ctx)?;
+
+        Ok(waiting_area.get_occupied_seats().into())
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let mut waiting_area = WaitingArea::new(input, false, 5);
+    fn part_two(&self, waiting_area: &WaitingArea, ctx: &Context) -> Result<Answer, SolveError> {
+        let mut waiting_area = Self::with_rules_of(waiting_area, 2, ctx)?;
+        waiting_area.settle(ctx)?;
 
-        while waiting_area.advance().is_ok() {}
+        Ok(waiting_area.get_occupied_seats().into())
+    }
 
-        waiting_area.get_occupied_seats().to_string()
+    fn frames(
+        &self,
+        waiting_area: &WaitingArea,
+        part: usize,
+        ctx: &Context,
+    ) -> Result<Vec<Frame>, SolveError> {
+        let waiting_area = Self::with_rules_of(waiting_area, part, ctx)?;
+        let rounds = waiting_area
+            .rules()
+            .generations_until_stable(waiting_area.seats.clone(), ctx)?;
+
+        Ok(rounds.iter().map(visualize::frame).collect())
     }
 }
 
+register!(
+    2020,
+    11,
+    Day11 {},
+    Puzzle {
+        title: "Seating System",
+        tags: &["cellular automaton", "grid", "line of sight"],
+        description: Some("Counts adjacent seats in part one, and the first visible seats in two"),
+    }
+);
+
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
+    use std::sync::Arc;
 
     use super::*;
+    use crate::context::{Event, Recorder};
+
+    impl WaitingArea {
+        /// Advances a round, failing if no seat changed
+        fn advance(&mut self) -> Result<(), ()> {
+            let seats = self.rules().step(&self.seats);
+
+            if seats == self.seats {
+                return Err(());
+            }
+
+            self.seats = seats;
+
+            Ok(())
+        }
+    }
 
     mod waiting_area {
         use super::WaitingArea;
@@ -281,8 +244,8 @@ LLLLLLLLLL
 L.LLLLLL.L
 L.LLLLL.LL";
 
-            let mut waiting_area = WaitingArea::new(start_area, true, 4);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, true, 4).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.##.##.##
 #######.##
@@ -311,8 +274,8 @@ L.LLLLL.LL";
 #.######.#
 #.#####.##";
 
-            let mut waiting_area = WaitingArea::new(start_area, true, 4);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, true, 4).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.LL.L#.##
 #LLLLLL.L#
@@ -341,8 +304,8 @@ L.L.L..L..
 #.LLLLLL.L
 #.#LLLL.##";
 
-            let mut waiting_area = WaitingArea::new(start_area, true, 4);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, true, 4).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.##.L#.##
 #L###LL.L#
@@ -371,8 +334,8 @@ L.#.#..#..
 #.LL###L.L
 #.#L###.##";
 
-            let mut waiting_area = WaitingArea::new(start_area, true, 4);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, true, 4).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.#L.L#.##
 #LLL#LL.L#
@@ -401,8 +364,8 @@ L.L.L..#..
 #.LLLLLL.L
 #.#L#L#.##";
 
-            let mut waiting_area = WaitingArea::new(start_area, true, 4);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, true, 4).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.#L.L#.##
 #LLL#LL.L#
@@ -431,8 +394,8 @@ L.#.L..#..
 #.LLLLLL.L
 #.#L#L#.##";
 
-            let mut waiting_area = WaitingArea::new(start_area, true, 4);
-            assert_eq!(waiting_area.advance().is_err(), true);
+            let mut waiting_area = WaitingArea::new(start_area, true, 4).unwrap();
+            assert!(waiting_area.advance().is_err());
 
             assert_eq!(format!("{}", waiting_area), start_area);
         }
@@ -450,8 +413,8 @@ LLLLLLLLLL
 L.LLLLLL.L
 L.LLLLL.LL";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.##.##.##
 #######.##
@@ -480,8 +443,8 @@ L.LLLLL.LL";
 #.######.#
 #.#####.##";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.LL.LL.L#
 #LLLLLL.LL
@@ -510,8 +473,8 @@ LLLLLLLLL#
 #.LLLLLL.L
 #.LLLLL.L#";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.L#.##.L#
 #L#####.LL
@@ -540,8 +503,8 @@ LLL####LL#
 #.L#####.L
 #.L####.L#";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.L#.L#.L#
 #LLLLLL.LL
@@ -570,8 +533,8 @@ LLLLLLLLL#
 #.LLLLL#.L
 #.L#LL#.L#";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.L#.L#.L#
 #LLLLLL.LL
@@ -600,8 +563,8 @@ LLL###LLL#
 #.LLLLL#.L
 #.L#LL#.L#";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_ok(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_ok());
 
             let expected_area = "#.L#.L#.L#
 #LLLLLL.LL
@@ -630,16 +593,18 @@ LLL###LLL#
 #.LLLLL#.L
 #.L#LL#.L#";
 
-            let mut waiting_area = WaitingArea::new(start_area, false, 5);
-            assert_eq!(waiting_area.advance().is_err(), true);
+            let mut waiting_area = WaitingArea::new(start_area, false, 5).unwrap();
+            assert!(waiting_area.advance().is_err());
 
             assert_eq!(format!("{}", waiting_area), start_area);
         }
     }
 
     #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
+    fn test_settle() {
+        let recorder = Arc::new(Recorder::default());
+        let ctx = Context::default().reporting_to(recorder.clone());
+        let mut waiting_area = WaitingArea::new(
             "L.LL.LL.LL
 LLLLLLL.LL
 L.L.L..L..
@@ -650,20 +615,25 @@ L.LLLLL.LL
 LLLLLLLLLL
 L.LLLLLL.L
 L.LLLLL.LL",
-        );
-        assert_eq!((Day11 {}).part_one(&input), "37");
-    }
+            true,
+            4,
+        )
+        .unwrap();
 
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/11.txt").expect("Could not read input file");
-        assert_eq!((Day11 {}).part_one(&input), "2468");
+        waiting_area.settle(&ctx).unwrap();
+
+        assert_eq!(waiting_area.get_occupied_seats(), 37);
+        assert_eq!(
+            recorder.events(),
+            vec![Event::Log(String::from("stable after 5 rounds"))]
+        );
     }
 
     #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "L.LL.LL.LL
+    fn test_frames() {
+        let waiting_area = Day11 {}
+            .parse(
+                "L.LL.LL.LL
 LLLLLLL.LL
 L.L.L..L..
 LLLL.LL.LL
@@ -673,13 +643,26 @@ L.LLLLL.LL
 LLLLLLLLLL
 L.LLLLLL.L
 L.LLLLL.LL",
-        );
-        assert_eq!((Day11 {}).part_two(&input), "26");
-    }
+            )
+            .unwrap();
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/11.txt").expect("Could not read input file");
-        assert_eq!((Day11 {}).part_two(&input), "2214");
+        let frames = Day11 {}
+            .frames(&waiting_area, 2, &Context::default())
+            .unwrap();
+
+        assert_eq!(frames.len(), 7);
+        assert_eq!(
+            frames[6].map(|_, cell| cell.glyph).to_string(),
+            "#.L#.L#.L#
+#LLLLLL.LL
+L.L.L..#..
+##L#.#L.L#
+L.L#.LL.L#
+#.LLLL#.LL
+..#.L.....
+LLL###LLL#
+#.LLLLL#.L
+#.L#LL#.L#"
+        );
     }
 }
@@ -1,31 +1,37 @@
-use std::convert::TryInto;
-use std::ops::{AddAssign, SubAssign};
-
-use crate::AdventOfCode;
-
-#[derive(Default)]
-struct Coordinate {
-    x: isize,
-    y: isize,
+use crate::error::{parse_lines, parse_number};
+use crate::utils::{Coordinate, Grid};
+use crate::visualize::{self, Colour, Draw};
+use crate::{register, Answer, Context, Frame, Puzzle, Solution, SolveError};
+
+/// How many tiles the longer side of the track is scaled to fit in when it's drawn
+const TRACK_SIZE: isize = 64;
+
+/// A tile of the sea the ship sails, as drawn with its track
+#[derive(Clone, Copy)]
+enum Tile {
+    Sea,
+    Wake,
+    Ship,
 }
 
-impl AddAssign<Coordinate> for Coordinate {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+impl Draw for Tile {
+    fn glyph(&self) -> char {
+        match *self {
+            Tile::Sea => '~',
+            Tile::Wake => '.',
+            Tile::Ship => '@',
         }
     }
-}
 
-impl SubAssign<Coordinate> for Coordinate {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+    fn colour(&self) -> Colour {
+        match *self {
+            Tile::Sea => Colour::rgb(20, 50, 110),
+            Tile::Wake => Colour::rgb(150, 200, 230),
+            Tile::Ship => Colour::rgb(250, 200, 40),
         }
     }
 }
+
 enum Rotation {
     Right,
     Left,
@@ -70,12 +76,10 @@ impl Ship {
     }
 
     fn get_manhattan_distance(&self) -> usize {
-        (self.coordinate.x.abs() + self.coordinate.y.abs())
-            .try_into()
-            .unwrap()
+        self.coordinate.manhattan_distance()
     }
 
-    fn advance(&mut self, action: &str, value: isize) {
+    fn advance(&mut self, action: &str, value: isize) -> Result<(), SolveError> {
         match action {
             "L" => {
                 for _ in 0..(value / 90) {
@@ -102,8 +106,10 @@ impl Ship {
                 }
             }
 
-            _ => panic!("Unrecognized instruction"),
+            _ => return Err(SolveError::invalid("action", action)),
         };
+
+        Ok(())
     }
 }
 
@@ -133,12 +139,10 @@ impl WaypointShip {
     }
 
     fn get_manhattan_distance(&self) -> usize {
-        (self.coordinate.x.abs() + self.coordinate.y.abs())
-            .try_into()
-            .unwrap()
+        self.coordinate.manhattan_distance()
     }
 
-    fn advance(&mut self, action: &str, value: isize) {
+    fn advance(&mut self, action: &str, value: isize) -> Result<(), SolveError> {
         match action {
             "L" => match value {
                 0 => {}
@@ -160,7 +164,7 @@ impl WaypointShip {
                         y: -self.waypoint.coordinate.x,
                     }
                 }
-                _ => panic!("Unsupported degree"),
+                _ => return Err(SolveError::invalid("degree", &value.to_string())),
             },
             "R" => match value {
                 0 => {}
@@ -182,7 +186,7 @@ impl WaypointShip {
                         y: self.waypoint.coordinate.x,
                     }
                 }
-                _ => panic!("Unsupported degree"),
+                _ => return Err(SolveError::invalid("degree", &value.to_string())),
             },
 
             "N" => self.waypoint.coordinate += Coordinate { x: 0, y: value },
@@ -197,80 +201,175 @@ impl WaypointShip {
                 }
             }
 
-            _ => panic!("Unrecognized instruction"),
+            _ => return Err(SolveError::invalid("action", action)),
         };
+
+        Ok(())
     }
 }
 
 pub struct Day12 {}
 
-impl AdventOfCode for Day12 {
-    fn part_one(&self, input: &str) -> String {
-        let mut ship = Ship::new();
+impl Day12 {
+    fn parse_instruction(line: &str) -> Result<(String, isize), SolveError> {
+        if !line.is_char_boundary(1) {
+            return Err(SolveError::invalid("instruction", line));
+        }
 
-        input.lines().for_each(|line| {
-            let action = &line[0..1];
-            let value = line[1..].parse::<isize>().expect("Not a number");
+        let (action, value) = line.split_at(1);
 
-            ship.advance(action, value)
-        });
+        Ok((action.to_string(), parse_number::<isize>(value)?))
+    }
+
+    /// Every position the ship moves to, starting where it sets off, leaving out the instructions
+    /// that don't move it. The instruction is given to the ship, which returns where it is after
+    fn track<F>(
+        instructions: &[(String, isize)],
+        mut advance: F,
+    ) -> Result<Vec<Coordinate>, SolveError>
+    where
+        F: FnMut(&str, isize) -> Result<Coordinate, SolveError>,
+    {
+        let mut track = vec![Coordinate::default()];
+
+        for (index, (action, value)) in instructions.iter().enumerate() {
+            let position = advance(action, *value).map_err(|error| error.on_line(index))?;
+
+            if track.last() != Some(&position) {
+                track.push(position);
+            }
+        }
 
-        ship.get_manhattan_distance().to_string()
+        Ok(track)
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let mut ship = WaypointShip::new();
+    /// Draws the track a frame per move, scaled down to fit within the track size. North is up,
+    /// and the cells between two positions are filled in by the wake
+    fn draw(track: &[Coordinate]) -> Result<Vec<Frame>, SolveError> {
+        let min_x = track.iter().map(|position| position.x).min().unwrap_or(0);
+        let max_x = track.iter().map(|position| position.x).max().unwrap_or(0);
+        let min_y = track.iter().map(|position| position.y).min().unwrap_or(0);
+        let max_y = track.iter().map(|position| position.y).max().unwrap_or(0);
+
+        let scale = (max_x - min_x).max(max_y - min_y) / TRACK_SIZE + 1;
+        let tile = |position: &Coordinate| {
+            Coordinate::new((position.x - min_x) / scale, (max_y - position.y) / scale)
+        };
 
-        input.lines().for_each(|line| {
-            let action = &line[0..1];
-            let value = line[1..].parse::<isize>().expect("Not a number");
+        let mut sea = Grid::new(
+            ((max_x - min_x) / scale + 1) as usize,
+            ((max_y - min_y) / scale + 1) as usize,
+            Tile::Sea,
+        )?;
+        let mut from = tile(&track[0]);
+
+        Ok(track
+            .iter()
+            .map(|position| {
+                let to = tile(position);
+                let (dx, dy) = (to.x - from.x, to.y - from.y);
+                let steps = dx.abs().max(dy.abs()).max(1);
+
+                for step in 0..=steps {
+                    let along = |distance: isize| (distance * step) as f64 / steps as f64;
+                    let wake = Coordinate::new(
+                        from.x + along(dx).round() as isize,
+                        from.y + along(dy).round() as isize,
+                    );
+
+                    if let Some(tile) = sea.get_mut(wake) {
+                        *tile = Tile::Wake;
+                    }
+                }
 
-            ship.advance(action, value)
-        });
+                from = to;
 
-        ship.get_manhattan_distance().to_string()
+                let mut frame = sea.clone();
+                if let Some(tile) = frame.get_mut(to) {
+                    *tile = Tile::Ship;
+                }
+
+                visualize::frame(&frame)
+            })
+            .collect())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "F10
-N3
-F7
-R90
-F11",
-        );
-        assert_eq!((Day12 {}).part_one(&input), "25");
+impl Solution for Day12 {
+    type Parsed = Vec<(String, isize)>;
+
+    fn parse(&self, input: &str) -> Result<Vec<(String, isize)>, SolveError> {
+        parse_lines(input, Self::parse_instruction)
     }
 
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/12.txt").expect("Could not read input file");
-        assert_eq!((Day12 {}).part_one(&input), "879");
+    fn part_one(
+        &self,
+        instructions: &Vec<(String, isize)>,
+        _ctx: &Context,
+    ) -> Result<Answer, SolveError> {
+        let mut ship = Ship::new();
+
+        for (index, (action, value)) in instructions.iter().enumerate() {
+            ship.advance(action, *value)
+                .map_err(|error| error.on_line(index))?;
+        }
+
+        Ok(ship.get_manhattan_distance().into())
     }
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "F10
-N3
-F7
-R90
-F11",
-        );
-        assert_eq!((Day12 {}).part_two(&input), "286");
+    fn part_two(
+        &self,
+        instructions: &Vec<(String, isize)>,
+        _ctx: &Context,
+    ) -> Result<Answer, SolveError> {
+        let mut ship = WaypointShip::new();
+
+        for (index, (action, value)) in instructions.iter().enumerate() {
+            ship.advance(action, *value)
+                .map_err(|error| error.on_line(index))?;
+        }
+
+        Ok(ship.get_manhattan_distance().into())
     }
 
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/12.txt").expect("Could not read input file");
-        assert_eq!((Day12 {}).part_two(&input), "18107");
+    /// A frame for every move of the ship, drawn over the wake it leaves behind
+    fn frames(
+        &self,
+        instructions: &Vec<(String, isize)>,
+        part: usize,
+        _ctx: &Context,
+    ) -> Result<Vec<Frame>, SolveError> {
+        let track = if part == 1 {
+            let mut ship = Ship::new();
+
+            Self::track(instructions, |action, value| {
+                ship.advance(action, value)?;
+
+                Ok(ship.coordinate)
+            })?
+        } else {
+            let mut ship = WaypointShip::new();
+
+            Self::track(instructions, |action, value| {
+                ship.advance(action, value)?;
+
+                Ok(ship.coordinate)
+            })?
+        };
+
+        Self::draw(&track)
     }
 }
+
+register!(
+    2020,
+    12,
+    Day12 {},
+    Puzzle {
+        title: "Rain Risk",
+        tags: &["simulation"],
+        description: Some(
+            "Moves the ship in part one, and the waypoint rotated around it in part two"
+        ),
+    }
+);
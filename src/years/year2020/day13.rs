@@ -1,74 +1,58 @@
-use crate::AdventOfCode;
+use crate::error::parse_number;
+use crate::utils::math;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day13 {}
 
-impl Day13 {
-    fn egcd(t: i64, id: i64) -> (i64, i64, i64) {
-        if t == 0 {
-            (id, 0, 1)
-        } else {
-            let (g, x, y) = Self::egcd(id % t, t);
-            (g, y - (id / t) * x, x)
-        }
-    }
-
-    fn mod_inv(t: i64, id: i64) -> Option<i64> {
-        let (g, x, _) = Self::egcd(t, id);
-        if g == 1 {
-            Some((x % id + id) % id)
-        } else {
-            None
-        }
-    }
-
-    fn first_consecutive_departure(residues: &[i64], modulii: &[i64]) -> Option<i64> {
-        let prod = modulii.iter().product::<i64>();
-
-        let mut sum = 0;
+pub struct Notes {
+    arrival: usize,
+    /// The bus ids in the order they're listed, `None` for busses that are out of service
+    busses: Vec<Option<usize>>,
+}
 
-        for (&residue, &modulus) in residues.iter().zip(modulii) {
-            let p = prod / modulus;
-            sum += residue * Self::mod_inv(p, modulus)? * p
-        }
+impl Solution for Day13 {
+    type Parsed = Notes;
 
-        Some(sum % prod)
-    }
-}
+    fn parse(&self, input: &str) -> Result<Notes, SolveError> {
+        let (arrival, busses) = input
+            .split_once('\n')
+            .ok_or_else(|| SolveError::missing("bus schedule"))?;
 
-impl AdventOfCode for Day13 {
-    fn part_one(&self, input: &str) -> String {
-        let mut splits = input.splitn(2, '\n');
-        let arrival = splits
-            .next()
-            .unwrap()
-            .parse::<usize>()
-            .expect("Not a number");
+        let arrival = parse_number::<usize>(arrival).map_err(|error| error.on_line(0))?;
 
-        let busses = splits
-            .next()
-            .unwrap()
+        let busses = busses
+            .trim_end()
             .split(',')
-            .filter(|&bus| bus != "x")
-            .map(|bus| bus.parse::<usize>().expect("Not a number"));
+            .map(|bus| match bus {
+                "x" => Ok(None),
+                bus => match parse_number::<usize>(bus) {
+                    Ok(0) => Err(SolveError::invalid("bus", bus)),
+                    bus => bus.map(Some),
+                },
+            })
+            .collect::<Result<Vec<Option<usize>>, SolveError>>()
+            .map_err(|error| error.on_line(1))?;
+
+        Ok(Notes { arrival, busses })
+    }
 
-        let next_bus_after_arrival = busses
-            .map(|bus| (bus, bus - arrival % bus))
+    fn part_one(&self, notes: &Notes, _ctx: &Context) -> Result<Answer, SolveError> {
+        notes
+            .busses
+            .iter()
+            .flatten()
+            .map(|&bus| (bus, bus - notes.arrival % bus))
             .min_by(|(_, a), (_, b)| a.cmp(b))
-            .unwrap();
-
-        (next_bus_after_arrival.0 * next_bus_after_arrival.1).to_string()
+            .ok_or_else(|| SolveError::missing("busses"))
+            .map(|(bus, wait)| Answer::from(bus * wait))
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let departures: Vec<(usize, i64)> = input
-            .splitn(2, '\n')
-            .nth(1)
-            .unwrap()
-            .split(',')
+    fn part_two(&self, notes: &Notes, _ctx: &Context) -> Result<Answer, SolveError> {
+        let departures = notes
+            .busses
+            .iter()
             .enumerate()
-            .filter(|&(_, bus)| bus != "x" && bus != "0")
-            .map(|(index, bus)| (index, bus.parse::<i64>().expect("Not a number")))
-            .collect();
+            .filter_map(|(index, &bus)| Some((index, bus? as i64)));
 
         let mut modulii = Vec::new();
         let mut residues = Vec::new();
@@ -77,45 +61,19 @@ impl AdventOfCode for Day13 {
             residues.push(time - (i as i64 % time));
         }
 
-        Self::first_consecutive_departure(residues.as_slice(), &modulii.as_slice())
-            .expect("modulii not pairwise coprime")
-            .to_string()
+        math::crt(residues.as_slice(), modulii.as_slice())
+            .ok_or_else(|| SolveError::no_solution("modulii not pairwise coprime"))
+            .map(Answer::from)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "939
-7,13,x,x,59,x,31,19",
-        );
-        assert_eq!((Day13 {}).part_one(&input), "295");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/13.txt").expect("Could not read input file");
-        assert_eq!((Day13 {}).part_one(&input), "2215");
+register!(
+    2020,
+    13,
+    Day13 {},
+    Puzzle {
+        title: "Shuttle Search",
+        tags: &["CRT", "modular arithmetic"],
+        description: Some("Lines up the departures with the Chinese remainder theorem"),
     }
-
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "939
-7,13,x,x,59,x,31,19",
-        );
-        assert_eq!((Day13 {}).part_two(&input), "1068781");
-    }
-
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/13.txt").expect("Could not read input file");
-        assert_eq!((Day13 {}).part_two(&input), "1058443396696792");
-    }
-}
+);
@@ -1,12 +1,43 @@
-use crate::AdventOfCode;
+use crate::error::parse_number;
+use crate::{register, Answer, Context, Param, Puzzle, Solution, SolveError};
+
+const PART_ONE_TURNS: Param = Param {
+    name: "part_one_turns",
+    default: "2020",
+    description: "The turn to find the spoken number of in part one",
+};
+
+const PART_TWO_TURNS: Param = Param {
+    name: "part_two_turns",
+    default: "30000000",
+    description: "The turn to find the spoken number of in part two",
+};
+
+/// The number of turns between checks for cancellation, and reports of the progress
+const CHECK_INTERVAL: usize = 1 << 20;
 
 struct CountingGame {
     end_at: usize,
 }
 
 impl CountingGame {
-    fn play(&self, start_numbers: Vec<usize>) -> usize {
-        let mut spoken_numbers = vec![0; self.end_at];
+    fn play(&self, start_numbers: &[usize], ctx: &Context) -> Result<usize, SolveError> {
+        if start_numbers.is_empty() {
+            return Err(SolveError::missing("start numbers"));
+        }
+
+        if self.end_at == 0 {
+            return Err(SolveError::invalid("turn", "0"));
+        }
+
+        // The game may end while the start numbers are still being spoken
+        if self.end_at <= start_numbers.len() {
+            return Ok(start_numbers[self.end_at - 1]);
+        }
+
+        // Start numbers may be larger than any number spoken during the game
+        let largest_start_number = start_numbers.iter().copied().max().unwrap_or(0);
+        let mut spoken_numbers = vec![0; self.end_at.max(largest_start_number + 1)];
 
         start_numbers
             .iter()
@@ -15,21 +46,22 @@ impl CountingGame {
                 spoken_numbers[number] = index + 1;
             });
 
-        let last_spoken_start_number = start_numbers
-            .last()
-            .copied()
-            .expect("The starting list was empty");
-
-        (start_numbers.len()..self.end_at).fold(
-            last_spoken_start_number,
+        (start_numbers.len()..self.end_at).try_fold(
+            start_numbers[start_numbers.len() - 1],
             |last_spoken_number, current_turn| {
+                // Checking for cancellation every turn would slow down the game noticeably
+                if current_turn % CHECK_INTERVAL == 0 {
+                    ctx.check()?;
+                    ctx.progress(current_turn, self.end_at);
+                }
+
                 let last_spoken_turn = spoken_numbers[last_spoken_number];
                 spoken_numbers[last_spoken_number] = current_turn;
 
                 if last_spoken_turn != 0 {
-                    current_turn - last_spoken_turn
+                    Ok(current_turn - last_spoken_turn)
                 } else {
-                    0
+                    Ok(0)
                 }
             },
         )
@@ -38,99 +70,162 @@ impl CountingGame {
 
 pub struct Day15 {}
 
-impl AdventOfCode for Day15 {
-    fn part_one(&self, input: &str) -> String {
-        let start_numbers: Vec<usize> = input
-            .split(',')
-            .map(|start_number| {
-                start_number
-                    .parse::<usize>()
-                    .expect("Start number was not a number")
-            })
-            .collect();
+impl Solution for Day15 {
+    type Parsed = Vec<usize>;
+
+    fn params(&self) -> &'static [Param] {
+        &[PART_ONE_TURNS, PART_TWO_TURNS]
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, SolveError> {
+        let input = input.trim();
 
-        let game = CountingGame { end_at: 2020 };
+        if input.is_empty() {
+            return Err(SolveError::missing("start numbers"));
+        }
 
-        game.play(start_numbers).to_string()
+        input.split(',').map(parse_number::<usize>).collect()
     }
 
-    fn part_two(&self, input: &str) -> String {
-        let start_numbers: Vec<usize> = input
-            .split(',')
-            .map(|start_number| {
-                start_number
-                    .parse::<usize>()
-                    .expect("Start number was not a number")
-            })
-            .collect();
+    fn part_one(&self, start_numbers: &Vec<usize>, ctx: &Context) -> Result<Answer, SolveError> {
+        let game = CountingGame {
+            end_at: ctx.param(&PART_ONE_TURNS)?,
+        };
 
-        let game = CountingGame { end_at: 30_000_000 };
+        Ok(game.play(start_numbers, ctx)?.into())
+    }
+
+    fn part_two(&self, start_numbers: &Vec<usize>, ctx: &Context) -> Result<Answer, SolveError> {
+        let game = CountingGame {
+            end_at: ctx.param(&PART_TWO_TURNS)?,
+        };
 
-        game.play(start_numbers).to_string()
+        Ok(game.play(start_numbers, ctx)?.into())
     }
 }
 
+register!(
+    2020,
+    15,
+    Day15 {},
+    Puzzle {
+        title: "Rambunctious Recitation",
+        tags: &["Van Eck sequence", "simulation"],
+        description: Some("A flat vector of last turns, as a hash map is too slow for 30M turns"),
+    }
+);
+
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
+    use std::sync::Arc;
 
     use super::*;
+    use crate::context::{Event, Recorder};
+    use crate::ErrorKind;
 
     #[test]
-    fn test_examples_part_one() {
-        let input = String::from("1,3,2");
-        assert_eq!((Day15 {}).part_one(&input), "1");
-
-        let input = String::from("2,1,3");
-        assert_eq!((Day15 {}).part_one(&input), "10");
-
-        let input = String::from("1,2,3");
-        assert_eq!((Day15 {}).part_one(&input), "27");
-
-        let input = String::from("2,3,1");
-        assert_eq!((Day15 {}).part_one(&input), "78");
+    fn test_no_start_numbers() {
+        let game = CountingGame { end_at: 10 };
 
-        let input = String::from("3,2,1");
-        assert_eq!((Day15 {}).part_one(&input), "438");
+        assert_eq!(
+            game.play(&[], &Context::default()).unwrap_err().kind,
+            ErrorKind::Missing("start numbers")
+        );
+    }
 
-        let input = String::from("3,1,2");
-        assert_eq!((Day15 {}).part_one(&input), "1836");
+    #[test]
+    fn test_few_turns() {
+        let play = |end_at| CountingGame { end_at }.play(&[0, 3, 6], &Context::default());
+
+        assert_eq!(play(1), Ok(0));
+        assert_eq!(play(2), Ok(3));
+        assert_eq!(play(3), Ok(6));
+        assert_eq!(play(4), Ok(0));
+        assert_eq!(play(0).unwrap_err().kind, ErrorKind::Invalid("turn"));
     }
 
     #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/15.txt").expect("Could not read input file");
-        assert_eq!((Day15 {}).part_one(&input), "234");
+    fn test_progress() {
+        let recorder = Arc::new(Recorder::default());
+        let ctx = Context::default().reporting_to(recorder.clone());
+        let game = CountingGame {
+            end_at: 2 * CHECK_INTERVAL + 1,
+        };
+
+        game.play(&[0, 3, 6], &ctx).unwrap();
+
+        assert_eq!(
+            recorder.events(),
+            vec![
+                Event::Progress {
+                    current: CHECK_INTERVAL,
+                    total: 2 * CHECK_INTERVAL + 1
+                },
+                Event::Progress {
+                    current: 2 * CHECK_INTERVAL,
+                    total: 2 * CHECK_INTERVAL + 1
+                },
+            ]
+        );
     }
 
     #[test]
     #[ignore = "Simply too slow to bother"]
     fn test_examples_part_two() {
         let input = String::from("0,3,6");
-        assert_eq!((Day15 {}).part_two(&input), "175594");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(175594)
+        );
 
         let input = String::from("1,3,2");
-        assert_eq!((Day15 {}).part_two(&input), "2578");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(2578)
+        );
 
         let input = String::from("2,1,3");
-        assert_eq!((Day15 {}).part_two(&input), "3544142");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(3544142)
+        );
 
         let input = String::from("1,2,3");
-        assert_eq!((Day15 {}).part_two(&input), "261214");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(261214)
+        );
 
         let input = String::from("2,3,1");
-        assert_eq!((Day15 {}).part_two(&input), "6895259");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(6895259)
+        );
 
         let input = String::from("3,2,1");
-        assert_eq!((Day15 {}).part_two(&input), "18");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(18)
+        );
 
         let input = String::from("3,1,2");
-        assert_eq!((Day15 {}).part_two(&input), "362");
-    }
-
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/15.txt").expect("Could not read input file");
-        assert_eq!((Day15 {}).part_two(&input), "8984");
+        assert_eq!(
+            (Day15 {})
+                .part_two(&(Day15 {}).parse(&input).unwrap(), &Context::default())
+                .unwrap(),
+            Answer::Integer(362)
+        );
     }
 }
@@ -1,55 +1,63 @@
 use std::collections::{HashMap, HashSet};
-use std::str::Lines;
 
-use crate::AdventOfCode;
+use crate::error::parse_number;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 type Rules = HashMap<String, Vec<usize>>;
 
 pub struct Day16 {}
 
+pub struct Notes {
+    rules: Rules,
+    your_ticket: Vec<usize>,
+    nearby_tickets: Vec<Vec<usize>>,
+}
+
 impl Day16 {
-    fn collect_rules(lines: Lines) -> Rules {
-        lines
+    fn collect_rules(input: &str, section: &str) -> Result<Rules, SolveError> {
+        section
+            .lines()
             .map(|line| {
-                let mut new_line = line.split(": ");
-
-                let rule_description = new_line
-                    .next()
-                    .expect("Expected to find a rule description")
-                    .to_string();
-
-                let all_viable_numbers = new_line
-                    .next()
-                    .map(|inner_rule| {
-                        inner_rule
-                            .split(" or ")
-                            .flat_map(|in_rule| {
-                                let bounds: Vec<usize> = in_rule
-                                    .split('-')
-                                    .map(|num| {
-                                        num.parse::<usize>().expect("Could not parse number")
-                                    })
-                                    .collect();
-
-                                (bounds[0]..=bounds[1]).collect::<Vec<usize>>()
-                            })
-                            .collect::<Vec<usize>>()
+                let (rule_description, inner_rule) = line
+                    .split_once(": ")
+                    .ok_or_else(|| SolveError::invalid("rule", line).within(input, line))?;
+
+                let all_viable_numbers = inner_rule
+                    .split(" or ")
+                    .map(|in_rule| {
+                        let (min, max) = in_rule
+                            .split_once('-')
+                            .ok_or_else(|| SolveError::invalid("range", in_rule))?;
+
+                        Ok((parse_number::<usize>(min)?..=parse_number::<usize>(max)?)
+                            .collect::<Vec<usize>>())
                     })
-                    .unwrap();
+                    .collect::<Result<Vec<Vec<usize>>, SolveError>>()
+                    .map_err(|error| error.within(input, line))?
+                    .concat();
 
-                (rule_description, all_viable_numbers)
+                Ok((rule_description.to_string(), all_viable_numbers))
             })
             .collect()
     }
 
-    fn get_valid_tickets(rules: &Rules, tickets: Vec<String>) -> Vec<Vec<usize>> {
-        tickets
-            .iter()
+    /// Parses the tickets of a section, skipping the heading
+    fn parse_tickets(input: &str, section: &str) -> Result<Vec<Vec<usize>>, SolveError> {
+        section
+            .lines()
+            .skip(1)
             .map(|line| {
                 line.split(',')
-                    .map(|number| number.parse::<usize>().expect("Not a number"))
-                    .collect::<Vec<usize>>()
+                    .map(parse_number::<usize>)
+                    .collect::<Result<Vec<usize>, SolveError>>()
+                    .map_err(|error| error.within(input, line))
             })
+            .collect()
+    }
+
+    fn get_valid_tickets(rules: &Rules, tickets: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        tickets
+            .iter()
             .filter(|ticket| {
                 ticket.iter().all(|number| {
                     rules
@@ -57,6 +65,7 @@ impl Day16 {
                         .any(|(_, valid_numbers)| valid_numbers.contains(number))
                 })
             })
+            .cloned()
             .collect()
     }
 
@@ -89,10 +98,17 @@ impl Day16 {
         let mut found_fields: HashMap<String, usize> = HashMap::new();
 
         // TODO: Determining which columns belongs to which rules could certainly be optimized
-        for _ in 0..=rules.len() {
-            if found_fields.len() >= rules.len() {
-                break;
-            }
+        let progress =
+            |found_fields: &HashMap<String, usize>,
+             possible_positions: &HashMap<usize, HashSet<String>>| {
+                let remaining = possible_positions.values().map(HashSet::len).sum::<usize>();
+
+                (found_fields.len(), remaining)
+            };
+
+        // Keep eliminating until every rule is found, or a round no longer narrows anything down
+        while found_fields.len() < rules.len() {
+            let before = progress(&found_fields, &possible_positions);
 
             possible_positions
                 .iter_mut()
@@ -107,6 +123,10 @@ impl Day16 {
                         *possible_rules = diff.cloned().collect();
                     }
                 });
+
+            if progress(&found_fields, &possible_positions) == before {
+                break;
+            }
         }
 
         match found_fields.len() {
@@ -123,13 +143,11 @@ impl Day16 {
             .collect()
     }
 
-    fn get_invalid_fields_from_all_tickets(rules: &Rules, tickets: Vec<String>) -> Vec<usize> {
+    fn get_invalid_fields_from_all_tickets(rules: &Rules, tickets: &[Vec<usize>]) -> Vec<usize> {
         tickets
             .iter()
-            .flat_map(|line| {
-                line.split(',')
-                    .map(|number| number.parse::<usize>().expect("Not a number"))
-            })
+            .flatten()
+            .copied()
             .filter(|number| {
                 rules
                     .iter()
@@ -139,127 +157,79 @@ impl Day16 {
     }
 }
 
-impl AdventOfCode for Day16 {
-    fn part_one(&self, input: &str) -> String {
+impl Solution for Day16 {
+    type Parsed = Notes;
+
+    fn parse(&self, input: &str) -> Result<Notes, SolveError> {
         let mut sections = input.split("\n\n");
 
         let rules = Self::collect_rules(
+            input,
             sections
                 .next()
-                .expect("Didn't find a rules section")
-                .lines(),
-        );
-
-        let nearby_tickets: Vec<String> = sections
-            .nth(1)
-            .expect("Didn't find any nearby tickets")
-            .lines()
-            .skip(1)
-            .map(|line| line.to_string())
-            .collect();
-
-        let invalid_numbers = Self::get_invalid_fields_from_all_tickets(&rules, nearby_tickets);
+                .ok_or_else(|| SolveError::missing("rules section"))?,
+        )?;
 
-        invalid_numbers.iter().sum::<usize>().to_string()
-    }
-
-    fn part_two(&self, input: &str) -> String {
-        let mut sections = input.split("\n\n");
+        let your_ticket = Self::parse_tickets(
+            input,
+            sections
+                .next()
+                .ok_or_else(|| SolveError::missing("your ticket section"))?,
+        )?
+        .concat();
 
-        let rules = Self::collect_rules(
+        let nearby_tickets = Self::parse_tickets(
+            input,
             sections
                 .next()
-                .expect("Didn't find a rules section")
-                .lines(),
-        );
+                .ok_or_else(|| SolveError::missing("nearby tickets section"))?,
+        )?;
+
+        Ok(Notes {
+            rules,
+            your_ticket,
+            nearby_tickets,
+        })
+    }
 
-        let your_ticket: Vec<usize> = sections
-            .next()
-            .expect("Didn't find your ticket")
-            .lines()
-            .skip(1)
-            .flat_map(|line| {
-                line.split(',')
-                    .map(|number| number.parse::<usize>().expect("Not a number"))
-            })
-            .collect();
+    fn part_one(&self, notes: &Notes, _ctx: &Context) -> Result<Answer, SolveError> {
+        let invalid_numbers =
+            Self::get_invalid_fields_from_all_tickets(&notes.rules, &notes.nearby_tickets);
 
-        let nearby_tickets: Vec<String> = sections
-            .next()
-            .expect("Didn't find any nearby tickets")
-            .lines()
-            .skip(1)
-            .map(|line| line.to_string())
-            .collect();
+        Ok(invalid_numbers.iter().sum::<usize>().into())
+    }
 
-        let valid_tickets = Self::get_valid_tickets(&rules, nearby_tickets);
+    fn part_two(&self, notes: &Notes, _ctx: &Context) -> Result<Answer, SolveError> {
+        let valid_tickets = Self::get_valid_tickets(&notes.rules, &notes.nearby_tickets);
 
-        match Self::calculate_rule_positions(&rules, valid_tickets) {
+        match Self::calculate_rule_positions(&notes.rules, valid_tickets) {
             Ok(rule_positions) => rule_positions
                 .iter()
                 .filter(|&(name, _)| name.starts_with("departure"))
-                .map(|(_, position)| your_ticket.get(*position).unwrap())
-                .product::<usize>()
-                .to_string(),
-            _ => panic!("Could not determine what rules your columns belonged to"),
+                .map(|(_, position)| {
+                    notes
+                        .your_ticket
+                        .get(*position)
+                        .ok_or_else(|| SolveError::missing("field on your ticket"))
+                })
+                .product::<Result<usize, SolveError>>()
+                .map(Answer::from),
+            _ => Err(SolveError::no_solution(
+                "could not determine what rules your columns belonged to",
+            )),
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::fs::read_to_string;
-
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "class: 1-3 or 5-7
-row: 6-11 or 33-44
-seat: 13-40 or 45-50
-
-your ticket:
-7,1,14
-
-nearby tickets:
-7,3,47
-40,4,50
-55,2,20
-38,6,12",
-        );
-        assert_eq!((Day16 {}).part_one(&input), "71");
-    }
-
-    #[test]
-    fn test_input_part_one() {
-        let input = read_to_string("data/2020/16.txt").expect("Could not read input file");
-        assert_eq!((Day16 {}).part_one(&input), "32842");
-    }
-
-    #[test]
-    fn test_examples_modified_part_two() {
-        // Modified input to contain departure on two of the rules
-        let input = String::from(
-            "departure_class: 0-1 or 4-19
-row: 0-5 or 8-19
-departure_seat: 0-13 or 16-19
-
-your ticket:
-11,12,13
-
-nearby tickets:
-3,9,18
-15,1,5
-5,14,9",
-        );
-
-        assert_eq!((Day16 {}).part_two(&input), "156");
+register!(
+    2020,
+    16,
+    Day16 {},
+    Puzzle {
+        title: "Ticket Translation",
+        tags: &["constraint propagation"],
+        description: Some(
+            "Settles positions with a single candidate rule until every rule is found"
+        ),
     }
-
-    #[test]
-    fn test_input_part_two() {
-        let input = read_to_string("data/2020/16.txt").expect("Could not read input file");
-        assert_eq!((Day16 {}).part_two(&input), "2628667251989");
-    }
-}
+);
@@ -1,47 +1,16 @@
-mod day01;
-pub use day01::Day01;
-
-mod day02;
-pub use day02::Day02;
-
-mod day03;
-pub use day03::Day03;
-
-mod day04;
-pub use day04::Day04;
-
-mod day05;
-pub use day05::Day05;
-
-mod day06;
-pub use day06::Day06;
-
-mod day07;
-pub use day07::Day07;
-
-mod day08;
-pub use day08::Day08;
-
-mod day09;
-pub use day09::Day09;
-
-mod day10;
-pub use day10::Day10;
-
-mod day11;
-pub use day11::Day11;
-
-mod day12;
-pub use day12::Day12;
-
-mod day13;
-pub use day13::Day13;
-
-mod day14;
-pub use day14::Day14;
-
-mod day15;
-pub use day15::Day15;
-
-mod day16;
-pub use day16::Day16;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
@@ -1,90 +1,54 @@
 use std::collections::BinaryHeap;
 
-use crate::AdventOfCode;
+use crate::error::parse_number;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day01;
 
-impl AdventOfCode for Day01 {
-    fn part_one(&self, input: &str) -> String {
+impl Solution for Day01 {
+    /// The total calories carried by each elf
+    type Parsed = Vec<usize>;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, SolveError> {
         input
             .split("\n\n")
             .map(|entry| {
                 entry
                     .lines()
-                    .map(|calories| calories.parse::<usize>().expect("Failed to parse calories"))
-                    .into_iter()
+                    .map(|calories| {
+                        parse_number::<usize>(calories)
+                            .map_err(|error| error.within(input, calories))
+                    })
                     .sum()
             })
-            .max()
-            .unwrap_or(0)
-            .to_string()
+            .collect()
     }
 
-    fn part_two(&self, input: &str) -> String {
-        input
-            .split("\n\n")
-            .map(|entry| {
-                entry
-                    .lines()
-                    .map(|calories| calories.parse::<usize>().expect("Failed to parse calories"))
-                    .into_iter()
-                    .sum()
-            })
+    fn part_one(&self, calories: &Vec<usize>, _ctx: &Context) -> Result<Answer, SolveError> {
+        Ok(calories.iter().max().copied().unwrap_or(0).into())
+    }
+
+    fn part_two(&self, calories: &Vec<usize>, _ctx: &Context) -> Result<Answer, SolveError> {
+        Ok(calories
+            .iter()
+            .copied()
             .collect::<BinaryHeap<usize>>()
             .iter()
             .take(3)
             .sum::<usize>()
-            .to_string()
+            .into())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "\
-1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000",
-        );
-
-        assert_eq!((Day01 {}).part_one(&input), "24000");
-    }
-
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "\
-1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000",
-        );
-
-        assert_eq!((Day01 {}).part_two(&input), "45000");
+register!(
+    2022,
+    1,
+    Day01 {},
+    Puzzle {
+        title: "Calorie Counting",
+        tags: &["sorting"],
+        description: Some(
+            "Totals the calories of each elf, and takes the largest totals from a binary heap"
+        ),
     }
-}
+);
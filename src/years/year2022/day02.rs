@@ -1,60 +1,54 @@
-use crate::AdventOfCode;
+use crate::error::parse_lines;
+use crate::{register, Answer, Context, Puzzle, Solution, SolveError};
 
 pub struct Day02;
 
-fn parse(entry: &str) -> (usize, usize) {
-    let (them, you) = entry.split_once(' ').expect("Invalid guide entry");
+fn parse(entry: &str) -> Result<(usize, usize), SolveError> {
+    let (them, you) = entry
+        .split_once(' ')
+        .ok_or_else(|| SolveError::invalid("guide entry", entry))?;
 
-    (
-        usize::from(them.as_bytes()[0] - b'A'),
-        usize::from(you.as_bytes()[0] - b'X'),
-    )
-}
-impl AdventOfCode for Day02 {
-    fn part_one(&self, input: &str) -> String {
-        input
-            .lines()
-            .map(parse)
-            .map(|(them, you)| (((4 + you - them) % 3) * 3) + (1 + you))
-            .sum::<usize>()
-            .to_string()
-    }
+    let shape = |text: &str, first: u8| match text.as_bytes() {
+        [byte] if (first..first + 3).contains(byte) => Ok(usize::from(byte - first)),
+        _ => Err(SolveError::invalid("guide entry", entry)),
+    };
 
-    fn part_two(&self, input: &str) -> String {
-        input
-            .lines()
-            .map(parse)
-            .map(|(them, result)| (result * 3) + (1 + ((result + them + 2) % 3)))
-            .sum::<usize>()
-            .to_string()
-    }
+    Ok((shape(them, b'A')?, shape(you, b'X')?))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Solution for Day02 {
+    type Parsed = Vec<(usize, usize)>;
 
-    #[test]
-    fn test_examples_part_one() {
-        let input = String::from(
-            "\
-A Y
-B X
-C Z",
-        );
+    fn parse(&self, input: &str) -> Result<Vec<(usize, usize)>, SolveError> {
+        parse_lines(input, parse)
+    }
 
-        assert_eq!((Day02 {}).part_one(&input), "15");
+    fn part_one(&self, guide: &Vec<(usize, usize)>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let score = guide
+            .iter()
+            .map(|&(them, you)| (((4 + you - them) % 3) * 3) + (1 + you))
+            .sum::<usize>();
+
+        Ok(score.into())
     }
 
-    #[test]
-    fn test_examples_part_two() {
-        let input = String::from(
-            "\
-A Y
-B X
-C Z",
-        );
+    fn part_two(&self, guide: &Vec<(usize, usize)>, _ctx: &Context) -> Result<Answer, SolveError> {
+        let score = guide
+            .iter()
+            .map(|&(them, result)| (result * 3) + (1 + ((result + them + 2) % 3)))
+            .sum::<usize>();
 
-        assert_eq!((Day02 {}).part_two(&input), "12");
+        Ok(score.into())
     }
 }
+
+register!(
+    2022,
+    2,
+    Day02 {},
+    Puzzle {
+        title: "Rock Paper Scissors",
+        tags: &["modular arithmetic"],
+        description: Some("Scores rounds by the difference of the shapes modulo 3"),
+    }
+);
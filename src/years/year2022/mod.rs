@@ -1,5 +1,2 @@
-mod day01;
-pub use day01::Day01;
-
-mod day02;
-pub use day02::Day02;
+pub mod day01;
+pub mod day02;
@@ -0,0 +1,91 @@
+mod common;
+
+use std::sync::Arc;
+
+use adventofcode::context::Event;
+use adventofcode::inputs::{Inputs, INPUT_DIR};
+use adventofcode::utils::{math, Coordinate};
+use adventofcode::years::year2020::day11::WaitingArea;
+use adventofcode::years::year2020::day13::Day13;
+use adventofcode::{registry, runner, Answer, Context, Params, Solution};
+
+use common::Recorder;
+
+const SEATS: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+#[test]
+fn test_run_registered_day() {
+    let registration = registry::find(2020, 1).unwrap();
+    let outcomes = runner::run(
+        registration,
+        &Inputs::new(INPUT_DIR).named(Some(String::from("example1"))),
+        &[1, 2],
+        &runner::Options::default(),
+    );
+
+    assert_eq!(outcomes[0].answer, Ok(Answer::Integer(514579)));
+    assert_eq!(outcomes[1].answer, Ok(Answer::Integer(241861950)));
+}
+
+#[test]
+fn test_run_with_params() {
+    let registration = registry::find(2020, 1).unwrap();
+    let options = runner::Options {
+        params: vec![(String::from("target"), String::from("1230"))]
+            .into_iter()
+            .collect::<Params>(),
+        ..runner::Options::default()
+    };
+
+    let outcomes = runner::run_input(registration, "220\n1010\n", &[1], &options);
+
+    assert_eq!(outcomes[0].answer, Ok(Answer::Integer(222200)));
+}
+
+#[test]
+fn test_solve_day_directly() {
+    let notes = Day13 {}.parse("939\n7,13,x,x,59,x,31,19\n").unwrap();
+
+    assert_eq!(
+        Day13 {}.part_two(&notes, &Context::default()),
+        Ok(Answer::Integer(1068781))
+    );
+}
+
+#[test]
+fn test_settle_waiting_area() {
+    let recorder = Arc::new(Recorder::default());
+    let ctx = Context::default().reporting_to(recorder.clone());
+
+    let mut waiting_area = WaitingArea::new(SEATS, false, 5).unwrap();
+    waiting_area.settle(&ctx).unwrap();
+
+    assert_eq!(waiting_area.get_occupied_seats(), 26);
+    assert_eq!(
+        recorder.events(),
+        vec![Event::Log(String::from("stable after 6 rounds"))]
+    );
+}
+
+#[test]
+fn test_utils() {
+    assert_eq!(
+        math::crt(&[0, 12, 55, 25, 12], &[7, 13, 59, 31, 19]),
+        Some(1068781)
+    );
+
+    let mut ship = Coordinate::default();
+    ship += Coordinate::new(17, 3);
+    ship -= Coordinate::new(0, 11);
+
+    assert_eq!(ship.manhattan_distance(), 25);
+}
@@ -0,0 +1,232 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_adventofcode"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_named_input() {
+    let output = run(&["--year", "2020", "--day", "9", "--input", "example1"]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "127\n62\n");
+}
+
+#[test]
+fn test_unknown_param() {
+    let output = run(&[
+        "-y", "2020", "-d", "1", "-i", "example1", "--param", "goal=1",
+    ]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown parameter goal"));
+}
+
+#[test]
+fn test_failing_part() {
+    let output = run(&[
+        "-y",
+        "2020",
+        "-d",
+        "15",
+        "-i",
+        "example1",
+        "--param",
+        "part_one_turns=abc",
+        "--param",
+        "part_two_turns=10",
+        "--time",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The other part is still solved and printed, before exiting with the failure
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "7\n");
+    assert!(stderr.contains("error: part 1: invalid part_one_turns"));
+    assert!(stderr.lines().any(|line| line.starts_with("part 1: ")));
+    assert!(stderr.lines().any(|line| line.starts_with("part 2: ")));
+}
+
+#[test]
+fn test_invalid_part() {
+    let output = run(&["-y", "2020", "-d", "1", "-i", "example1", "-p", "3"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("isn't a valid value for '--part"));
+}
+
+#[test]
+fn test_list_by_tag() {
+    let output = run(&["list", "--tag", "crt"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains("Shuttle Search"));
+
+    // The template new days are created from isn't a day of its own
+    let output = run(&["list"]);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Untitled"));
+}
+
+#[test]
+fn test_list_params() {
+    let output = run(&["list", "--year", "2020", "--params"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout
+        .lines()
+        .next()
+        .unwrap()
+        .starts_with("year  day  param"));
+    assert!(stdout
+        .lines()
+        .any(|line| line.starts_with("2020  15   part_two_turns      30000000")));
+}
+
+#[test]
+fn test_bench() {
+    let output = run(&["-y", "2020", "-d", "1", "-i", "example1", "--bench", "2"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(
+        !run(&["-y", "2020", "-d", "1", "-b", "2", "--format", "csv"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_visualize() {
+    let output = run(&[
+        "-y",
+        "2020",
+        "-d",
+        "11",
+        "-i",
+        "example1",
+        "--visualize",
+        "--frame-delay",
+        "0",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("2020/11 part 1 frame 6/6"));
+    assert!(stdout.contains("2020/11 part 2 frame 7/7"));
+
+    let output = run(&["-y", "2020", "-d", "1", "-i", "example1", "--visualize"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("nothing to visualize"));
+}
+
+#[test]
+fn test_export() {
+    let directory =
+        std::env::temp_dir().join(format!("adventofcode-export-{}", std::process::id()));
+    let gif = directory.join("day03.gif");
+    let export = |path: &std::path::Path| {
+        run(&[
+            "-y",
+            "2020",
+            "-d",
+            "3",
+            "-i",
+            "example1",
+            "--export",
+            path.to_str().unwrap(),
+        ])
+    };
+
+    let output = export(&gif);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("part 1: wrote 11 frames"));
+
+    // The same input always gives the same images
+    let first = std::fs::read(directory.join("day03-part1.gif")).unwrap();
+    assert!(export(&gif).status.success());
+    assert_eq!(
+        std::fs::read(directory.join("day03-part1.gif")).unwrap(),
+        first
+    );
+
+    let pngs = directory.join("pngs");
+    assert!(export(&pngs).status.success());
+    assert_eq!(std::fs::read_dir(pngs.join("part2")).unwrap().count(), 5);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_verify_without_answers() {
+    let output = Command::new(env!("CARGO_BIN_EXE_adventofcode"))
+        .arg("verify")
+        .current_dir(std::env::temp_dir())
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Could not read data/answers.toml"));
+}
+
+#[test]
+fn test_verify_input_dir() {
+    let dir = std::env::temp_dir().join(format!("adventofcode-verify-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let output = run(&[
+        "--input-dir",
+        dir.to_str().unwrap(),
+        "verify",
+        "--year",
+        "2020",
+        "--day",
+        "1",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains(&format!(
+        "Missing input {}",
+        dir.join("2020/01.txt").display()
+    )));
+    assert!(!stdout.contains("example1"));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_watch_after_restart() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    // A restarted watch is passed the answers of the last run before the rebuild
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adventofcode"))
+        .args(["-y", "2020", "-d", "1", "-i", "example1", "--watch"])
+        .env(
+            "AOC_WATCH_PREVIOUS_ANSWERS",
+            r#"[[["example1",1],{"Ok":"1"}]]"#,
+        )
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let lines = BufReader::new(child.stdout.take().unwrap())
+        .lines()
+        .take(3)
+        .collect::<Result<Vec<String>, _>>();
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let lines = lines.unwrap();
+    assert!(lines[1].starts_with("example1  1     514579     1"));
+    assert_eq!(lines[2].trim_end(), "example1  2     241861950");
+}
@@ -0,0 +1,19 @@
+use std::sync::Mutex;
+
+use adventofcode::context::{Event, Reporter};
+
+/// Records every reported event, so tests can assert on them
+#[derive(Default)]
+pub struct Recorder(Mutex<Vec<Event>>);
+
+impl Recorder {
+    pub fn events(&self) -> Vec<Event> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Reporter for Recorder {
+    fn report(&self, event: Event) {
+        self.0.lock().unwrap().push(event);
+    }
+}